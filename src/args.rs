@@ -4,6 +4,14 @@
 
 use clap::Parser;
 
+use crate::code_input::CodeLanguage;
+use crate::dictionary::Language;
+use crate::keyboard::KeyboardLayout;
+use crate::live_stats::LiveStat;
+use crate::markov_input::Generator;
+use crate::quote_input::QuoteLength;
+use crate::test_mode::TestMode;
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "donkeytype - a very minimalistic cli typing test", long_about = None)]
 pub struct Args {
@@ -11,6 +19,57 @@ pub struct Args {
     #[arg(short, long)]
     pub duration: Option<u64>,
 
+    /// mode of the test deciding when it ends
+    #[arg(long, value_enum)]
+    pub mode: Option<TestMode>,
+
+    /// amount of words to type in `words` mode, switches mode to `words` if mode is not provided
+    #[arg(long)]
+    pub words: Option<usize>,
+
+    /// length of the quotes to pick from in `quote` mode
+    #[arg(long, value_enum)]
+    pub quote_length: Option<QuoteLength>,
+
+    /// path to JSON file with quotes used in `quote` mode
+    #[arg(long)]
+    pub quotes_path: Option<String>,
+
+    /// path to text file to type verbatim, or `-` to read it from standard input, switches mode to
+    /// `text` if mode is not provided
+    #[arg(long)]
+    pub text: Option<String>,
+
+    /// indicates if line breaks of the text should be kept and typed with `<Enter>` in `text` mode
+    #[arg(long)]
+    pub keep_newlines: Option<bool>,
+
+    /// language of builtin code snippets used in `code` mode
+    #[arg(long, value_enum)]
+    pub code_language: Option<CodeLanguage>,
+
+    /// path to file with code to type in `code` mode, switches mode to `code` if mode is not
+    /// provided
+    #[arg(long)]
+    pub code_path: Option<String>,
+
+    /// indicates if indentation should be skipped automatically after a line break in `code` mode
+    #[arg(long)]
+    pub skip_indentation: Option<bool>,
+
+    /// indicates if words with letters and bigrams user is weak on should appear more often in
+    /// `time` and `words` modes
+    #[arg(long)]
+    pub adaptive: Option<bool>,
+
+    /// WPM needed to unlock next letter in `lesson` mode
+    #[arg(long)]
+    pub lesson_target_wpm: Option<f64>,
+
+    /// accuracy in percents needed to unlock next letter in `lesson` mode
+    #[arg(long)]
+    pub lesson_target_accuracy: Option<f64>,
+
     /// indicates if test should include numbers
     #[arg(short, long)]
     pub numbers: Option<bool>,
@@ -19,6 +78,10 @@ pub struct Args {
     #[arg(long)]
     pub numbers_ratio: Option<f64>,
 
+    /// comma separated formats of numbers, `#` is replaced with a random digit, e.g. `####,19##,#.##,###-###-###`
+    #[arg(long, value_delimiter = ',')]
+    pub number_formats: Option<Vec<String>>,
+
     /// indicates if test should include symbols
     #[arg(short, long)]
     pub symbols: Option<bool>,
@@ -27,10 +90,67 @@ pub struct Args {
     #[arg(long)]
     pub symbols_ratio: Option<f64>,
 
+    /// comma separated symbols appended to words, e.g. `->,::,=>,!=,&&`
+    #[arg(long, value_delimiter = ',')]
+    pub ending_symbols: Option<Vec<String>>,
+
+    /// comma separated pairs of symbols surrounding words, each pair is split in the middle, e.g. `(),[],<<>>`
+    #[arg(long, value_delimiter = ',')]
+    pub surrounding_symbols: Option<Vec<String>>,
+
+    /// indicates if words should be built into sentences with punctuation
+    #[arg(short, long)]
+    pub punctuation: Option<bool>,
+
+    /// sentence-end-ratio argument
+    #[arg(long)]
+    pub sentence_end_ratio: Option<f64>,
+
+    /// comma-ratio argument
+    #[arg(long)]
+    pub comma_ratio: Option<f64>,
+
+    /// apostrophe-ratio argument
+    #[arg(long)]
+    pub apostrophe_ratio: Option<f64>,
+
+    /// quote-ratio argument
+    #[arg(long)]
+    pub quote_ratio: Option<f64>,
+
     /// path to dictionary file
     #[arg(long)]
     pub dictionary_path: Option<String>,
 
+    /// language of the builtin dictionary used when dictionary path is not provided
+    #[arg(long, value_enum)]
+    pub language: Option<Language>,
+
+    /// minimal length of words sampled from the dictionary
+    #[arg(long)]
+    pub min_word_length: Option<usize>,
+
+    /// maximal length of words sampled from the dictionary
+    #[arg(long)]
+    pub max_word_length: Option<usize>,
+
+    /// comma separated words that should never appear in the test
+    #[arg(long, value_delimiter = ',')]
+    pub exclude_words: Option<Vec<String>>,
+
+    /// how the text is generated in `time` and `words` modes
+    #[arg(long, value_enum)]
+    pub generator: Option<Generator>,
+
+    /// path to text file the Markov chain is trained on, switches generator to `markov` if
+    /// generator is not provided
+    #[arg(long)]
+    pub corpus_path: Option<String>,
+
+    /// prints available languages of the builtin dictionary and exits
+    #[arg(long)]
+    pub list_languages: bool,
+
     /// indicates if test should include words beginning with uppercase letters
     #[arg(short, long)]
     pub uppercase: Option<bool>,
@@ -39,6 +159,22 @@ pub struct Args {
     #[arg(long)]
     pub uppercase_ratio: Option<f64>,
 
+    /// maximal width of the typed text in characters, the text is centred in wider terminals, `0` means no limit
+    #[arg(long)]
+    pub max_width: Option<u16>,
+
+    /// comma separated statistics displayed in the stats bar while typing
+    #[arg(long, value_delimiter = ',')]
+    pub live_stats: Option<Vec<LiveStat>>,
+
+    /// indicates if everything but the text should be hidden while typing
+    #[arg(long)]
+    pub focus: Option<bool>,
+
+    /// indicates if the test should start with the first typed character instead of the start key
+    #[arg(long)]
+    pub type_to_start: Option<bool>,
+
     // path to config file in json format
     #[arg(long)]
     pub config_path: Option<String>,
@@ -51,6 +187,14 @@ pub struct Args {
     #[arg(long, requires = "save_results")]
     pub results_path: Option<String>,
 
+    /// keyboard layout used to display statistics of each key
+    #[arg(long, value_enum)]
+    pub keyboard_layout: Option<KeyboardLayout>,
+
+    /// seed of the random generator, tests with the same seed and options have the same text
+    #[arg(long)]
+    pub seed: Option<u64>,
+
     /// Add subcommands here
     #[command(subcommand)]
     pub history: Option<SubCommand>,
@@ -60,6 +204,10 @@ pub struct Args {
 pub enum SubCommand {
     #[command(about = "Show previous test results in a bar chart.")]
     History(HistorySubcommandArgs),
+    #[command(
+        about = "Take the daily challenge, the same 30 seconds test for everyone on a given day."
+    )]
+    Daily(DailySubcommandArgs),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -68,3 +216,6 @@ pub struct HistorySubcommandArgs {
     // #[arg(short, long)]
     // pub show_date: Option<bool>,
 }
+
+#[derive(Parser, Debug, Clone)]
+pub struct DailySubcommandArgs {}
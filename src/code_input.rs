@@ -0,0 +1,143 @@
+//! Module creating the expected input for the test runner in `code` mode
+//!
+//! It picks a random snippet of `code_language` from the builtin code snippets,
+//! or reads the whole file if `code_path` is provided in config,
+//! and returns it preserving its line structure and indentation.
+//!
+//! Line breaks are typed with `<Enter>`.
+//! Indentation is skipped automatically after a line break if `skip_indentation` is set to true,
+//! otherwise it has to be typed with spaces or `<Tab>`.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+use crate::code_snippets;
+use crate::config::Config;
+use crate::expected_input::ExpectedInputInterface;
+use crate::helpers::{get_chars_range, get_rng};
+
+/// Amount of spaces a tab character in the code file is replaced with
+const TAB_WIDTH: usize = 4;
+
+/// Enum used in config for choosing the language of builtin code snippets.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeLanguage {
+    #[default]
+    Rust,
+    Python,
+    Javascript,
+}
+
+impl CodeLanguage {
+    fn get_snippets(&self) -> &'static [&'static str] {
+        match self {
+            CodeLanguage::Rust => code_snippets::RUST,
+            CodeLanguage::Python => code_snippets::PYTHON,
+            CodeLanguage::Javascript => code_snippets::JAVASCRIPT,
+        }
+    }
+}
+
+/// Struct used by runner to hold the code used for validation and as a placeholder
+#[derive(Debug)]
+pub struct ExpectedCode {
+    code: Vec<char>,
+}
+
+impl ExpectedCode {
+    /// Create new struct instance by reading the code file or picking a random builtin snippet
+    pub fn new(config: &Config) -> Result<Self> {
+        let code = match &config.code_path {
+            Some(code_path) => {
+                let mut str = String::new();
+                let mut file =
+                    std::fs::File::open(code_path).context("Unable to open code file")?;
+                file.read_to_string(&mut str)
+                    .context("Unable to read code file")?;
+                str
+            }
+            None => config
+                .code_language
+                .get_snippets()
+                .choose(&mut get_rng(config.seed))
+                .context("Unable to find code snippet")?
+                .to_string(),
+        };
+
+        let code = normalize_code(&code);
+        if code.is_empty() {
+            anyhow::bail!("Code file is empty");
+        }
+
+        Ok(Self {
+            code: code.chars().collect(),
+        })
+    }
+}
+
+/// Replaces tabs with spaces, removes trailing whitespace of each line
+/// and empty lines at the beginning and at the end of the code.
+fn normalize_code(code: &str) -> String {
+    code.lines()
+        .map(|line| {
+            line.replace('\t', &" ".repeat(TAB_WIDTH))
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+impl ExpectedInputInterface for ExpectedCode {
+    /// Returns characters of the code from the given range
+    ///
+    /// If the code ends before the end of the range, the rest of the code is returned.
+    fn get_range(&mut self, start: usize, end: usize) -> String {
+        get_chars_range(&self.code, start, end)
+    }
+
+    fn get_len(&self) -> Option<usize> {
+        Some(self.code.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn should_pick_snippet_of_given_language() {
+        let config = Config {
+            code_language: CodeLanguage::Python,
+            ..Config::default()
+        };
+        let mut expected_code = ExpectedCode::new(&config).expect("unable to create expected code");
+
+        assert!(code_snippets::PYTHON.contains(&expected_code.get_range(0, usize::MAX).as_str()));
+    }
+
+    #[test]
+    fn should_read_code_file_preserving_indentation() {
+        let mut code_file = tempfile::NamedTempFile::new().expect("Unable to create temp file");
+        code_file
+            .write_all("\nif x:  \n\treturn y\n\n".as_bytes())
+            .expect("Unable to write to temp file");
+        let config = Config {
+            code_path: Some(code_file.path().to_path_buf()),
+            ..Config::default()
+        };
+
+        let mut expected_code = ExpectedCode::new(&config).expect("unable to create expected code");
+
+        assert_eq!(expected_code.get_range(0, 100), "if x:\n    return y");
+        assert_eq!(expected_code.get_len(), Some(18));
+    }
+}
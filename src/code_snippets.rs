@@ -0,0 +1,81 @@
+//! module with code snippets used to generate expected input in `code` mode
+
+pub const RUST: &[&str] = &[
+    r#"fn main() {
+    let numbers = vec![1, 2, 3, 4, 5];
+    let sum: i32 = numbers.iter().sum();
+    println!("sum: {}", sum);
+}"#,
+    r#"impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn distance(&self, other: &Point) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+}"#,
+    r#"fn read_lines(path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content.lines().map(|line| line.to_string()).collect())
+}"#,
+    r#"match command {
+    Command::Start => runner.start(),
+    Command::Pause => runner.pause(),
+    Command::Quit => return Ok(()),
+}"#,
+];
+
+pub const PYTHON: &[&str] = &[
+    r#"def fibonacci(n):
+    a, b = 0, 1
+    for _ in range(n):
+        a, b = b, a + b
+    return a"#,
+    r#"class Stack:
+    def __init__(self):
+        self.items = []
+
+    def push(self, item):
+        self.items.append(item)
+
+    def pop(self):
+        return self.items.pop()"#,
+    r#"with open("data.txt") as file:
+    words = [line.strip() for line in file if line.strip()]
+print(len(words))"#,
+    r#"def count_words(text):
+    counts = {}
+    for word in text.split():
+        counts[word] = counts.get(word, 0) + 1
+    return counts"#,
+];
+
+pub const JAVASCRIPT: &[&str] = &[
+    r#"function debounce(callback, delay) {
+  let timeout;
+  return (...args) => {
+    clearTimeout(timeout);
+    timeout = setTimeout(() => callback(...args), delay);
+  };
+}"#,
+    r#"const total = items
+  .filter((item) => item.price > 0)
+  .reduce((sum, item) => sum + item.price, 0);"#,
+    r#"async function fetchUser(id) {
+  const response = await fetch(`/api/users/${id}`);
+  if (!response.ok) {
+    throw new Error("Unable to fetch user");
+  }
+  return response.json();
+}"#,
+    r#"export class Counter {
+  constructor() {
+    this.count = 0;
+  }
+
+  increment() {
+    this.count += 1;
+  }
+}"#,
+];
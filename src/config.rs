@@ -2,21 +2,53 @@
 //!
 //! Default options of configuration are:
 //!
-//! | name              | default value                | type in JSON | description                                                                                                                                                                                                           |
-//! | ----------------- | ---------------------------- | ------------ | --------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
-//! | `duration`        | `30`                         | number       | duration of the test in seconds                                                                                                                                                                                       |
-//! | `numbers`         | `false`                      | boolean      | flag indicating if numbers should be inserted in expected input                                                                                                                                                       |
-//! | `numbers_ratio`   | `0.05` (if numbers=true)     | number       | ratio for putting numbers in the test                                                                                                                                                                                 |
-//! | `symbols`         | `false`                      | boolean      | flag indicating if symbols should be inserted in expected input                                                                                                                                                       |
-//! | `symbols_ratio`   | `0.10` (if symbols=true)     | number       | ratio for putting symbols in the test                                                                                                                                                                                 |
-//! | `uppercase`       | `false`                      | boolean      | flag indicating if uppercase letters should be inserted in expected input                                                                                                                                             |
-//! | `uppercase_ratio` | `0.15` (if uppercase=true)   | boolean      | ratio for putting uppercase letters in test                                                                                                                                                                           |
-//! | `dictionary_path` |  `None` (builtin dictionary) | string       | path to file with dictionary words to sample from while creating test's expected input                                                                                                                                |
-//! | `save_results`    | `true`                       | boolean      | flag indicating if results should be saved to a file  ( `~/.local/share/donkeytype/donkeytype-results.csv`  on Linux and MacOS, and `C:\Users\{Username}\AppData\Local\donkeytype\donkeytype-results.csv` on Windows) |
+//! | name                     | default value                      | type in JSON    | description                                                                                                                                                                                                                                                                                                                                                                       |
+//! | ------------------------ | ---------------------------------- | --------------- | --------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+//! | `duration`               | `30`                               | number          | duration of the test in seconds                                                                                                                                                                                                                                                                                                                                                   |
+//! | `mode`                   | `"time"`                           | string          | mode of the test, `"time"` ends the test after `duration` seconds, `"words"` once `words` words were typed, `"quote"` once the whole quote was typed, `"text"` once the whole `text_path` file was typed or `duration` passed, `"code"` once the whole code snippet was typed, `"zen"` once `<Enter>` was pressed, `"lesson"` once `words` words from unlocked letters were typed |
+//! | `words`                  | `50`                               | number          | amount of words to type in `words` and `lesson` modes                                                                                                                                                                                                                                                                                                                             |
+//! | `quote_length`           | `"all"`                            | string          | length of the quotes to pick from in `quote` mode, one of `"all"`, `"short"`, `"medium"`, `"long"`                                                                                                                                                                                                                                                                                |
+//! | `quotes_path`            | `None` (builtin quotes)            | string          | path to JSON file with quotes (`[{ "text": "...", "source": "..." }]`) to pick from in `quote` mode                                                                                                                                                                                                                                                                               |
+//! | `text_path`              | `None`                             | string          | path to text file to type verbatim in `text` mode, `"-"` reads the text from standard input                                                                                                                                                                                                                                                                                       |
+//! | `keep_newlines`          | `false`                            | boolean         | flag indicating if line breaks of the text should be kept and typed with `<Enter>` in `text` mode                                                                                                                                                                                                                                                                                 |
+//! | `code_language`          | `"rust"`                           | string          | language of builtin code snippets in `code` mode, one of `"rust"`, `"python"`, `"javascript"`                                                                                                                                                                                                                                                                                     |
+//! | `code_path`              | `None` (builtin snippets)          | string          | path to file with code to type in `code` mode                                                                                                                                                                                                                                                                                                                                     |
+//! | `skip_indentation`       | `true`                             | boolean         | flag indicating if indentation should be skipped automatically after typing a line break in `code` mode                                                                                                                                                                                                                                                                           |
+//! | `adaptive`               | `false`                            | boolean         | flag indicating if words with letters and bigrams you make most mistakes on or type slowest should appear more often in `time` and `words` modes                                                                                                                                                                                                                                  |
+//! | `lesson_target_wpm`      | `35`                               | number          | WPM needed to unlock next letter in `lesson` mode                                                                                                                                                                                                                                                                                                                                 |
+//! | `lesson_target_accuracy` | `95`                               | number          | accuracy in percents, before corrections, needed to unlock next letter in `lesson` mode                                                                                                                                                                                                                                                                                           |
+//! | `numbers`                | `false`                            | boolean         | flag indicating if numbers should be inserted in expected input                                                                                                                                                                                                                                                                                                                   |
+//! | `numbers_ratio`          | `0.05` (if numbers=true)           | number          | ratio for putting numbers in the test                                                                                                                                                                                                                                                                                                                                             |
+//! | `number_formats`         | `[]`                               | list of strings | formats of numbers, `#` is replaced with a random digit, e.g. `19##` or `#.##`, by default numbers are as long as replaced words                                                                                                                                                                                                                                                  |
+//! | `symbols`                | `false`                            | boolean         | flag indicating if symbols should be inserted in expected input                                                                                                                                                                                                                                                                                                                   |
+//! | `symbols_ratio`          | `0.10` (if symbols=true)           | number          | ratio for putting symbols in the test                                                                                                                                                                                                                                                                                                                                             |
+//! | `ending_symbols`         | `[".", ",", "!", "?"]`             | list of strings | symbols appended to words, e.g. `->` or `::`                                                                                                                                                                                                                                                                                                                                      |
+//! | `surrounding_symbols`    | `["[]", "{}", "()", "\"\"", "''"]` | list of strings | pairs of symbols surrounding words, each pair is split in the middle, e.g. `<<>>`                                                                                                                                                                                                                                                                                                 |
+//! | `punctuation`            | `false`                            | boolean         | flag indicating if words should be built into sentences, starting with a capital letter and ending with `.`, `?` or `!`, with commas, apostrophes and quotes                                                                                                                                                                                                                      |
+//! | `sentence_end_ratio`     | `0.12` (if punctuation=true)       | number          | ratio of words ending a sentence with `.`, `?` or `!` in punctuation mode                                                                                                                                                                                                                                                                                                         |
+//! | `comma_ratio`            | `0.08` (if punctuation=true)       | number          | ratio of words followed by a comma in punctuation mode                                                                                                                                                                                                                                                                                                                            |
+//! | `apostrophe_ratio`       | `0.03` (if punctuation=true)       | number          | ratio of words getting an apostrophe (`"dog's"`) in punctuation mode                                                                                                                                                                                                                                                                                                              |
+//! | `quote_ratio`            | `0.03` (if punctuation=true)       | number          | ratio of words opening a quote around a few words in punctuation mode                                                                                                                                                                                                                                                                                                             |
+//! | `uppercase`              | `false`                            | boolean         | flag indicating if uppercase letters should be inserted in expected input                                                                                                                                                                                                                                                                                                         |
+//! | `uppercase_ratio`        | `0.15` (if uppercase=true)         | boolean         | ratio for putting uppercase letters in test                                                                                                                                                                                                                                                                                                                                       |
+//! | `max_width`              | `80`                               | number          | maximal width of the typed text in characters, the text is centred in wider terminals, `0` means no limit                                                                                                                                                                                                                                                                         |
+//! | `live_stats`             | `[]`                               | list of strings | statistics displayed in the stats bar while typing, any of `"wpm"`, `"raw_wpm"`, `"accuracy"` and `"errors"`                                                                                                                                                                                                                                                                      |
+//! | `focus`                  | `false`                            | boolean         | flag indicating if everything but the text, including time left and stats bar, should be hidden while typing                                                                                                                                                                                                                                                                      |
+//! | `type_to_start`          | `false`                            | boolean         | flag indicating if the test should start with the first typed character, counted as input, instead of the start key, the timer starts with that character and the start key only resumes a paused test                                                                                                                                                                            |
+//! | `dictionary_path`        | `None` (builtin dictionary)        | string          | path to file with dictionary words, one per line, optionally followed by a tab and the frequency of the word (`word<TAB>count`), frequent words are sampled more often                                                                                                                                                                                                            |
+//! | `language`               | `"english"`                        | string          | language of the builtin dictionary used when `dictionary_path` is not set, one of `"english_200"`, `"english"`, `"german"`, `"spanish"`, `"french"`, `"polish"`, `"russian"`, run with `--list-languages` to see them all                                                                                                                                                         |
+//! | `min_word_length`        | `None`                             | number          | minimal length in characters of words sampled from the dictionary                                                                                                                                                                                                                                                                                                                 |
+//! | `max_word_length`        | `None`                             | number          | maximal length in characters of words sampled from the dictionary                                                                                                                                                                                                                                                                                                                 |
+//! | `exclude_words`          | `[]`                               | array           | words that should never appear in the test, e.g. `["the", "a"]`, in arguments separated with commas                                                                                                                                                                                                                                                                               |
+//! | `generator`              | `"words"`                          | string          | how the text is generated in `time` and `words` modes, `"words"` samples random words from the dictionary, `"markov"` generates sentences with a Markov chain trained on `corpus_path`                                                                                                                                                                                            |
+//! | `corpus_path`            | `None` (builtin corpus)            | string          | path to text file the Markov chain is trained on when `generator` is `"markov"`                                                                                                                                                                                                                                                                                                   |
+//! | `save_results`           | `true`                             | boolean         | flag indicating if results should be saved to a file ( `~/.local/share/donkeytype/donkeytype-results.csv` on Linux and MacOS, and `C:\Users\{Username}\AppData\Local\donkeytype\donkeytype-results.csv` on Windows)                                                                                                                                                               |
+//! | `keyboard_layout`        | `"qwerty"`                         | string          | keyboard layout of the per key statistics diagram, one of `"qwerty"`, `"dvorak"`, `"colemak"`                                                                                                                                                                                                                                                                                     |
+//! | `seed`                   | `None` (random)                    | number          | seed of the random generator used to generate the test, tests with the same seed and options have the same text, seed of each test is saved in its results                                                                                                                                                                                                                        |
 //!
-//! NOTE: If provided `numbers_ratio` is not between `0` to `1.0`, default `numbers_ratio = 0.15` will be used. Same happens with `uppercase_ratio` and `symbols_ratio`.
+//! NOTE: If provided `numbers_ratio` is not between `0` to `1.0`, default `numbers_ratio = 0.15` will be used. Same happens with `uppercase_ratio`, `symbols_ratio` and the ratios of punctuation mode.
 //!
-//! Configuration will grow when more features are added (_different modes_, _different languages_).
+//! Configuration will grow when more features are added (_different modes_).
 //!
 //! You can provide this config by putting it in a config file in `~/.config/donkeytype/donkeytype-config.json`:
 //!
@@ -33,12 +65,23 @@
 //!         "correct_match_bg": "white",
 //!         "incorrect_match_fg": "#ff00ff",
 //!         "incorrect_match_bg": "#0f000f"
+//!     },
+//!     "keybindings": {
+//!         "start": ["s", "enter"],
+//!         "quit": ["q", "ctrl+c"],
+//!         "restart": ["r", "ctrl+r"]
 //!     }
 //! }
 //!
 //! > Note: Providing config in a file supports passing custom color values.
 //!
-//! Apart from `colors` you can set configuration by passing options when running the program:
+//! Keys can be bound to actions `start`, `quit`, `help`, `restart`, `settings`, `pause`,
+//! `word_delete`, `repeat`, `next` and `toggle_heatmap`, and to actions of the settings window
+//! `select_previous`, `select_next`, `previous_value`, `next_value`, `save_settings` and `close`,
+//! see the `keybindings` module for the format of keys.
+//! The same key can't be bound to two actions available on the same screen.
+//!
+//! Apart from `colors` and `keybindings` you can set configuration by passing options when running the program:
 //!
 //! ```shell
 //! cargo run -- --duration 60 --dictionary-path "/usr/share/dict/words" --numbers true
@@ -56,39 +99,120 @@ use mockall::*;
 use serde::{Deserialize, Serialize};
 use std::{fs, io::Read, path::PathBuf, time::Duration};
 
+use crate::code_input::CodeLanguage;
 use crate::color_scheme::ColorScheme;
+use crate::dictionary::Language;
+use crate::expected_input::{DEFAULT_ENDING_SYMBOLS, DEFAULT_SURROUNDING_SYMBOLS};
+use crate::keybindings::{parse_keys, Keybindings};
+use crate::keyboard::KeyboardLayout;
+use crate::live_stats::LiveStat;
+use crate::markov_input::Generator;
+use crate::quote_input::QuoteLength;
+use crate::test_mode::TestMode;
 use crate::Args;
 
 /// Main program configuration
 #[derive(Debug, Clone)]
 pub struct Config {
     pub duration: Duration,
+    pub mode: TestMode,
+    pub words: usize,
+    pub quote_length: QuoteLength,
+    pub quotes_path: Option<PathBuf>,
+    pub text_path: Option<PathBuf>,
+    pub keep_newlines: bool,
+    pub code_language: CodeLanguage,
+    pub code_path: Option<PathBuf>,
+    pub skip_indentation: bool,
+    pub adaptive: bool,
+    pub lesson_target_wpm: f64,
+    pub lesson_target_accuracy: f64,
     pub numbers: bool,
     pub numbers_ratio: f64,
+    pub number_formats: Vec<String>,
     pub symbols: bool,
     pub symbols_ratio: f64,
+    pub ending_symbols: Vec<String>,
+    pub surrounding_symbols: Vec<String>,
+    pub punctuation: bool,
+    pub sentence_end_ratio: f64,
+    pub comma_ratio: f64,
+    pub apostrophe_ratio: f64,
+    pub quote_ratio: f64,
     pub dictionary_path: Option<PathBuf>,
+    pub language: Language,
+    pub min_word_length: Option<usize>,
+    pub max_word_length: Option<usize>,
+    pub exclude_words: Vec<String>,
+    pub generator: Generator,
+    pub corpus_path: Option<PathBuf>,
     pub uppercase: bool,
     pub uppercase_ratio: f64,
+    pub max_width: u16,
+    pub live_stats: Vec<LiveStat>,
+    pub focus: bool,
+    pub type_to_start: bool,
     pub colors: ColorScheme,
+    pub keybindings: Keybindings,
     pub save_results: bool,
     pub results_path: Option<PathBuf>,
+    pub keyboard_layout: KeyboardLayout,
+    pub seed: Option<u64>,
+    /// set by the `daily` subcommand, can't be set in config file nor arguments
+    pub daily: bool,
+    /// path of the config file, options changed in the settings window are saved to it
+    pub config_file_path: PathBuf,
+    /// text read from standard input when `text_path` is `-`, see `text_input::read_stdin_text`
+    pub stdin_text: Option<String>,
 }
 
 /// Used by `serde` crate to parse config file into a rust struct
 #[derive(Deserialize, Serialize, Debug)]
 struct ConfigFile {
     pub duration: Option<u64>,
+    pub mode: Option<TestMode>,
+    pub words: Option<usize>,
+    pub quote_length: Option<QuoteLength>,
+    pub quotes_path: Option<String>,
+    pub text_path: Option<String>,
+    pub keep_newlines: Option<bool>,
+    pub code_language: Option<CodeLanguage>,
+    pub code_path: Option<String>,
+    pub skip_indentation: Option<bool>,
+    pub adaptive: Option<bool>,
+    pub lesson_target_wpm: Option<f64>,
+    pub lesson_target_accuracy: Option<f64>,
     pub numbers: Option<bool>,
     pub numbers_ratio: Option<f64>,
+    pub number_formats: Option<Vec<String>>,
     pub symbols: Option<bool>,
     pub symbols_ratio: Option<f64>,
+    pub ending_symbols: Option<Vec<String>>,
+    pub surrounding_symbols: Option<Vec<String>>,
+    pub punctuation: Option<bool>,
+    pub sentence_end_ratio: Option<f64>,
+    pub comma_ratio: Option<f64>,
+    pub apostrophe_ratio: Option<f64>,
+    pub quote_ratio: Option<f64>,
     pub dictionary_path: Option<String>,
+    pub language: Option<Language>,
+    pub min_word_length: Option<usize>,
+    pub max_word_length: Option<usize>,
+    pub exclude_words: Option<Vec<String>>,
+    pub generator: Option<Generator>,
+    pub corpus_path: Option<String>,
     pub uppercase: Option<bool>,
     pub uppercase_ratio: Option<f64>,
+    pub max_width: Option<u16>,
+    pub live_stats: Option<Vec<LiveStat>>,
+    pub focus: Option<bool>,
+    pub type_to_start: Option<bool>,
     pub colors: Option<ConfigFileColorScheme>,
+    pub keybindings: Option<ConfigFileKeybindings>,
     pub save_results: Option<bool>,
     pub results_path: Option<String>,
+    pub keyboard_layout: Option<KeyboardLayout>,
+    pub seed: Option<u64>,
 }
 
 /// Struct used be `serde` crate to parse colors config from config file
@@ -100,22 +224,86 @@ struct ConfigFileColorScheme {
     pub incorrect_match_bg: Option<String>,
 }
 
+/// Struct used be `serde` crate to parse keybindings config from config file
+#[derive(Deserialize, Serialize, Debug)]
+struct ConfigFileKeybindings {
+    pub start: Option<Vec<String>>,
+    pub quit: Option<Vec<String>>,
+    pub help: Option<Vec<String>>,
+    pub restart: Option<Vec<String>>,
+    pub settings: Option<Vec<String>>,
+    pub pause: Option<Vec<String>>,
+    pub word_delete: Option<Vec<String>>,
+    pub finish: Option<Vec<String>>,
+    pub repeat: Option<Vec<String>>,
+    pub next: Option<Vec<String>>,
+    pub toggle_heatmap: Option<Vec<String>>,
+    pub select_previous: Option<Vec<String>>,
+    pub select_next: Option<Vec<String>>,
+    pub previous_value: Option<Vec<String>>,
+    pub next_value: Option<Vec<String>>,
+    pub save_settings: Option<Vec<String>>,
+    pub close: Option<Vec<String>>,
+}
+
 #[automock]
 impl Config {
     /// Provide default values for configuration options
     pub fn default() -> Self {
         Self {
             duration: Duration::from_secs(30),
+            mode: TestMode::Time,
+            words: 50,
+            quote_length: QuoteLength::All,
+            quotes_path: None,
+            text_path: None,
+            keep_newlines: false,
+            code_language: CodeLanguage::Rust,
+            code_path: None,
+            skip_indentation: true,
+            adaptive: false,
+            lesson_target_wpm: 35.0,
+            lesson_target_accuracy: 95.0,
             numbers: false,
             numbers_ratio: 0.05,
+            number_formats: vec![],
             symbols: false,
             symbols_ratio: 0.10,
+            ending_symbols: DEFAULT_ENDING_SYMBOLS
+                .iter()
+                .map(|symbol| symbol.to_string())
+                .collect(),
+            surrounding_symbols: DEFAULT_SURROUNDING_SYMBOLS
+                .iter()
+                .map(|symbols| symbols.to_string())
+                .collect(),
+            punctuation: false,
+            sentence_end_ratio: 0.12,
+            comma_ratio: 0.08,
+            apostrophe_ratio: 0.03,
+            quote_ratio: 0.03,
             dictionary_path: None,
+            language: Language::English,
+            min_word_length: None,
+            max_word_length: None,
+            exclude_words: vec![],
+            generator: Generator::Words,
+            corpus_path: None,
             uppercase: false,
             uppercase_ratio: 0.15,
+            max_width: 80,
+            live_stats: vec![],
+            focus: false,
+            type_to_start: false,
             colors: ColorScheme::default(),
+            keybindings: Keybindings::default(),
             save_results: true,
             results_path: None,
+            keyboard_layout: KeyboardLayout::Qwerty,
+            seed: None,
+            daily: false,
+            config_file_path: PathBuf::new(),
+            stdin_text: None,
         }
     }
 
@@ -135,12 +323,75 @@ impl Config {
                     .context("Unable to augment config with config file")?;
             }
             augment_config_with_args(&mut config, args);
+            config.config_file_path = config_file_path;
+            config
+                .keybindings
+                .validate()
+                .context("Invalid keybindings")?;
 
             config
         };
 
         Ok(config)
     }
+
+    /// Saves options that can be changed in the settings window to the config file
+    ///
+    /// Other options already set in the config file are kept as they are.
+    pub fn save_settings(&self) -> Result<()> {
+        let mut options = match open_config_file_if_exists(self.config_file_path.clone())
+            .context("Unable to open config file")?
+        {
+            Some(mut config_file) => {
+                let mut config_file_content = String::new();
+                config_file
+                    .read_to_string(&mut config_file_content)
+                    .context("Unable to read file")?;
+                serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(
+                    &config_file_content,
+                )
+                .context("Unable to parse config file")?
+            }
+            None => serde_json::Map::new(),
+        };
+
+        let settings = serde_json::json!({
+            "mode": self.mode,
+            "duration": self.duration.as_secs(),
+            "words": self.words,
+            "language": self.language,
+            "numbers": self.numbers,
+            "numbers_ratio": self.numbers_ratio,
+            "symbols": self.symbols,
+            "symbols_ratio": self.symbols_ratio,
+            "uppercase": self.uppercase,
+            "uppercase_ratio": self.uppercase_ratio,
+        });
+        if let serde_json::Value::Object(settings) = settings {
+            options.extend(settings);
+        }
+        match &self.dictionary_path {
+            Some(dictionary_path) => {
+                options.insert(
+                    "dictionary_path".to_string(),
+                    serde_json::json!(dictionary_path),
+                );
+            }
+            None => {
+                options.remove("dictionary_path");
+            }
+        }
+
+        if let Some(config_dir) = self.config_file_path.parent() {
+            fs::create_dir_all(config_dir).context("Unable to create config directory")?;
+        }
+        let config_file_content =
+            serde_json::to_string_pretty(&options).context("Unable to serialize config")?;
+        fs::write(&self.config_file_path, config_file_content)
+            .context("Unable to write config file")?;
+
+        Ok(())
+    }
 }
 
 /// Overwrite provided config with options parsed from configuration file
@@ -158,40 +409,174 @@ fn augment_config_with_config_file(config: &mut Config, mut config_file: fs::Fil
             config.duration = Duration::from_secs(duration);
         }
 
+        if let Some(mode) = config_from_file.mode {
+            config.mode = mode;
+        }
+
+        if let Some(words) = config_from_file.words {
+            if words > 0 {
+                config.words = words;
+            }
+        }
+
+        if let Some(quote_length) = config_from_file.quote_length {
+            config.quote_length = quote_length;
+        }
+
+        if let Some(quotes_path) = config_from_file.quotes_path {
+            config.quotes_path = Some(PathBuf::from(quotes_path));
+        }
+
+        if let Some(text_path) = config_from_file.text_path {
+            config.text_path = Some(PathBuf::from(text_path));
+        }
+
+        if let Some(keep_newlines) = config_from_file.keep_newlines {
+            config.keep_newlines = keep_newlines;
+        }
+
+        if let Some(code_language) = config_from_file.code_language {
+            config.code_language = code_language;
+        }
+
+        if let Some(code_path) = config_from_file.code_path {
+            config.code_path = Some(PathBuf::from(code_path));
+        }
+
+        if let Some(skip_indentation) = config_from_file.skip_indentation {
+            config.skip_indentation = skip_indentation;
+        }
+
+        if let Some(adaptive) = config_from_file.adaptive {
+            config.adaptive = adaptive;
+        }
+
+        if let Some(lesson_target_wpm) = config_from_file.lesson_target_wpm {
+            if lesson_target_wpm > 0.0 {
+                config.lesson_target_wpm = lesson_target_wpm;
+            }
+        }
+
+        if let Some(lesson_target_accuracy) = config_from_file.lesson_target_accuracy {
+            if (0.0..=100.0).contains(&lesson_target_accuracy) {
+                config.lesson_target_accuracy = lesson_target_accuracy;
+            }
+        }
+
         if let Some(numbers) = config_from_file.numbers {
             config.numbers = numbers;
         }
 
         if let Some(numbers_ratio) = config_from_file.numbers_ratio {
-            if numbers_ratio >= 0.0 && numbers_ratio <= 1.0 {
+            if (0.0..=1.0).contains(&numbers_ratio) {
                 config.numbers_ratio = numbers_ratio
             }
         }
 
+        if let Some(number_formats) = config_from_file.number_formats {
+            config.number_formats = number_formats;
+        }
+
         if let Some(symbols) = config_from_file.symbols {
             config.symbols = symbols;
         }
 
         if let Some(symbols_ratio) = config_from_file.symbols_ratio {
-            if symbols_ratio >= 0.0 && symbols_ratio <= 1.0 {
+            if (0.0..=1.0).contains(&symbols_ratio) {
                 config.symbols_ratio = symbols_ratio;
             }
         }
 
+        if let Some(ending_symbols) = config_from_file.ending_symbols {
+            config.ending_symbols = ending_symbols;
+        }
+
+        if let Some(surrounding_symbols) = config_from_file.surrounding_symbols {
+            config.surrounding_symbols = surrounding_symbols;
+        }
+
+        if let Some(punctuation) = config_from_file.punctuation {
+            config.punctuation = punctuation;
+        }
+
+        if let Some(sentence_end_ratio) = config_from_file.sentence_end_ratio {
+            if (0.0..=1.0).contains(&sentence_end_ratio) {
+                config.sentence_end_ratio = sentence_end_ratio;
+            }
+        }
+
+        if let Some(comma_ratio) = config_from_file.comma_ratio {
+            if (0.0..=1.0).contains(&comma_ratio) {
+                config.comma_ratio = comma_ratio;
+            }
+        }
+
+        if let Some(apostrophe_ratio) = config_from_file.apostrophe_ratio {
+            if (0.0..=1.0).contains(&apostrophe_ratio) {
+                config.apostrophe_ratio = apostrophe_ratio;
+            }
+        }
+
+        if let Some(quote_ratio) = config_from_file.quote_ratio {
+            if (0.0..=1.0).contains(&quote_ratio) {
+                config.quote_ratio = quote_ratio;
+            }
+        }
+
         if let Some(dictionary_path) = config_from_file.dictionary_path {
             config.dictionary_path = Some(PathBuf::from(dictionary_path));
         }
 
+        if let Some(language) = config_from_file.language {
+            config.language = language;
+        }
+
+        if let Some(min_word_length) = config_from_file.min_word_length {
+            config.min_word_length = Some(min_word_length);
+        }
+
+        if let Some(max_word_length) = config_from_file.max_word_length {
+            config.max_word_length = Some(max_word_length);
+        }
+
+        if let Some(exclude_words) = config_from_file.exclude_words {
+            config.exclude_words = exclude_words;
+        }
+
+        if let Some(generator) = config_from_file.generator {
+            config.generator = generator;
+        }
+
+        if let Some(corpus_path) = config_from_file.corpus_path {
+            config.corpus_path = Some(PathBuf::from(corpus_path));
+        }
+
         if let Some(uppercase) = config_from_file.uppercase {
             config.uppercase = uppercase;
         }
 
         if let Some(uppercase_ratio) = config_from_file.uppercase_ratio {
-            if uppercase_ratio >= 0.0 && uppercase_ratio <= 1.0 {
+            if (0.0..=1.0).contains(&uppercase_ratio) {
                 config.uppercase_ratio = uppercase_ratio
             }
         }
 
+        if let Some(max_width) = config_from_file.max_width {
+            config.max_width = max_width;
+        }
+
+        if let Some(live_stats) = config_from_file.live_stats {
+            config.live_stats = live_stats;
+        }
+
+        if let Some(focus) = config_from_file.focus {
+            config.focus = focus;
+        }
+
+        if let Some(type_to_start) = config_from_file.type_to_start {
+            config.type_to_start = type_to_start;
+        }
+
         if let Some(colors) = config_from_file.colors {
             if let Some(correct_match_fg) = colors.correct_match_fg {
                 config.colors.correct_match_fg = correct_match_fg.parse().unwrap();
@@ -210,6 +595,76 @@ fn augment_config_with_config_file(config: &mut Config, mut config_file: fs::Fil
             }
         }
 
+        if let Some(keybindings) = config_from_file.keybindings {
+            if let Some(start) = keybindings.start {
+                config.keybindings.start = parse_keys(&start)?;
+            }
+
+            if let Some(quit) = keybindings.quit {
+                config.keybindings.quit = parse_keys(&quit)?;
+            }
+
+            if let Some(help) = keybindings.help {
+                config.keybindings.help = parse_keys(&help)?;
+            }
+
+            if let Some(restart) = keybindings.restart {
+                config.keybindings.restart = parse_keys(&restart)?;
+            }
+
+            if let Some(settings) = keybindings.settings {
+                config.keybindings.settings = parse_keys(&settings)?;
+            }
+
+            if let Some(pause) = keybindings.pause {
+                config.keybindings.pause = parse_keys(&pause)?;
+            }
+
+            if let Some(word_delete) = keybindings.word_delete {
+                config.keybindings.word_delete = parse_keys(&word_delete)?;
+            }
+
+            if let Some(finish) = keybindings.finish {
+                config.keybindings.finish = parse_keys(&finish)?;
+            }
+
+            if let Some(repeat) = keybindings.repeat {
+                config.keybindings.repeat = parse_keys(&repeat)?;
+            }
+
+            if let Some(next) = keybindings.next {
+                config.keybindings.next = parse_keys(&next)?;
+            }
+
+            if let Some(toggle_heatmap) = keybindings.toggle_heatmap {
+                config.keybindings.toggle_heatmap = parse_keys(&toggle_heatmap)?;
+            }
+
+            if let Some(select_previous) = keybindings.select_previous {
+                config.keybindings.select_previous = parse_keys(&select_previous)?;
+            }
+
+            if let Some(select_next) = keybindings.select_next {
+                config.keybindings.select_next = parse_keys(&select_next)?;
+            }
+
+            if let Some(previous_value) = keybindings.previous_value {
+                config.keybindings.previous_value = parse_keys(&previous_value)?;
+            }
+
+            if let Some(next_value) = keybindings.next_value {
+                config.keybindings.next_value = parse_keys(&next_value)?;
+            }
+
+            if let Some(save_settings) = keybindings.save_settings {
+                config.keybindings.save_settings = parse_keys(&save_settings)?;
+            }
+
+            if let Some(close) = keybindings.close {
+                config.keybindings.close = parse_keys(&close)?;
+            }
+        }
+
         if let Some(save_results) = config_from_file.save_results {
             config.save_results = save_results;
         }
@@ -219,6 +674,14 @@ fn augment_config_with_config_file(config: &mut Config, mut config_file: fs::Fil
                 config.results_path = Some(PathBuf::from(path));
             }
         }
+
+        if let Some(keyboard_layout) = config_from_file.keyboard_layout {
+            config.keyboard_layout = keyboard_layout;
+        }
+
+        if let Some(seed) = config_from_file.seed {
+            config.seed = Some(seed);
+        }
     }
 
     Ok(())
@@ -230,7 +693,7 @@ fn open_config_file_if_exists(config_file: PathBuf) -> Result<Option<fs::File>>
         return Ok(Some(config_file));
     }
 
-    return Ok(None);
+    Ok(None)
 }
 
 /// Overwrite provided config with values from args object
@@ -239,32 +702,140 @@ fn augment_config_with_args(config: &mut Config, args: Args) {
         config.numbers = numbers_flag;
     }
     if let Some(numbers_ratio) = args.numbers_ratio {
-        if numbers_ratio >= 0.0 && numbers_ratio <= 1.0 {
+        if (0.0..=1.0).contains(&numbers_ratio) {
             config.numbers_ratio = numbers_ratio
         }
     }
+    if let Some(number_formats) = args.number_formats {
+        config.number_formats = number_formats;
+    }
     if let Some(symbols) = args.symbols {
         config.symbols = symbols;
     }
     if let Some(symbols_ratio) = args.symbols_ratio {
-        if symbols_ratio >= 0.0 && symbols_ratio <= 1.0 {
+        if (0.0..=1.0).contains(&symbols_ratio) {
             config.symbols_ratio = symbols_ratio
         }
     }
+    if let Some(ending_symbols) = args.ending_symbols {
+        config.ending_symbols = ending_symbols;
+    }
+    if let Some(surrounding_symbols) = args.surrounding_symbols {
+        config.surrounding_symbols = surrounding_symbols;
+    }
+    if let Some(punctuation) = args.punctuation {
+        config.punctuation = punctuation;
+    }
+    if let Some(sentence_end_ratio) = args.sentence_end_ratio {
+        if (0.0..=1.0).contains(&sentence_end_ratio) {
+            config.sentence_end_ratio = sentence_end_ratio;
+        }
+    }
+    if let Some(comma_ratio) = args.comma_ratio {
+        if (0.0..=1.0).contains(&comma_ratio) {
+            config.comma_ratio = comma_ratio;
+        }
+    }
+    if let Some(apostrophe_ratio) = args.apostrophe_ratio {
+        if (0.0..=1.0).contains(&apostrophe_ratio) {
+            config.apostrophe_ratio = apostrophe_ratio;
+        }
+    }
+    if let Some(quote_ratio) = args.quote_ratio {
+        if (0.0..=1.0).contains(&quote_ratio) {
+            config.quote_ratio = quote_ratio;
+        }
+    }
     if let Some(duration) = args.duration {
         config.duration = Duration::from_secs(duration);
     }
+    if let Some(words) = args.words {
+        if words > 0 {
+            config.words = words;
+            config.mode = TestMode::Words;
+        }
+    }
+    if let Some(text_path) = args.text {
+        config.text_path = Some(PathBuf::from(text_path));
+        config.mode = TestMode::Text;
+    }
+    if let Some(keep_newlines) = args.keep_newlines {
+        config.keep_newlines = keep_newlines;
+    }
+    if let Some(code_language) = args.code_language {
+        config.code_language = code_language;
+    }
+    if let Some(code_path) = args.code_path {
+        config.code_path = Some(PathBuf::from(code_path));
+        config.mode = TestMode::Code;
+    }
+    if let Some(skip_indentation) = args.skip_indentation {
+        config.skip_indentation = skip_indentation;
+    }
+    if let Some(adaptive) = args.adaptive {
+        config.adaptive = adaptive;
+    }
+    if let Some(lesson_target_wpm) = args.lesson_target_wpm {
+        if lesson_target_wpm > 0.0 {
+            config.lesson_target_wpm = lesson_target_wpm;
+        }
+    }
+    if let Some(lesson_target_accuracy) = args.lesson_target_accuracy {
+        if (0.0..=100.0).contains(&lesson_target_accuracy) {
+            config.lesson_target_accuracy = lesson_target_accuracy;
+        }
+    }
+    if let Some(mode) = args.mode {
+        config.mode = mode;
+    }
+    if let Some(quote_length) = args.quote_length {
+        config.quote_length = quote_length;
+    }
+    if let Some(quotes_path) = args.quotes_path {
+        config.quotes_path = Some(PathBuf::from(quotes_path));
+    }
     if let Some(dictionary_path) = args.dictionary_path {
         config.dictionary_path = Some(PathBuf::from(dictionary_path));
     }
+    if let Some(language) = args.language {
+        config.language = language;
+    }
+    if let Some(min_word_length) = args.min_word_length {
+        config.min_word_length = Some(min_word_length);
+    }
+    if let Some(max_word_length) = args.max_word_length {
+        config.max_word_length = Some(max_word_length);
+    }
+    if let Some(exclude_words) = args.exclude_words {
+        config.exclude_words = exclude_words;
+    }
+    if let Some(corpus_path) = args.corpus_path {
+        config.corpus_path = Some(PathBuf::from(corpus_path));
+        config.generator = Generator::Markov;
+    }
+    if let Some(generator) = args.generator {
+        config.generator = generator;
+    }
     if let Some(uppercase_flag) = args.uppercase {
         config.uppercase = uppercase_flag
     }
     if let Some(uppercase_ratio) = args.uppercase_ratio {
-        if uppercase_ratio >= 0.0 && uppercase_ratio <= 1.0 {
+        if (0.0..=1.0).contains(&uppercase_ratio) {
             config.uppercase_ratio = uppercase_ratio
         }
     }
+    if let Some(max_width) = args.max_width {
+        config.max_width = max_width;
+    }
+    if let Some(live_stats) = args.live_stats {
+        config.live_stats = live_stats;
+    }
+    if let Some(focus) = args.focus {
+        config.focus = focus;
+    }
+    if let Some(type_to_start) = args.type_to_start {
+        config.type_to_start = type_to_start;
+    }
     if let Some(save_results_flag) = args.save_results {
         config.save_results = save_results_flag;
     }
@@ -274,11 +845,19 @@ fn augment_config_with_args(config: &mut Config, args: Args) {
             config.results_path = Some(PathBuf::from(path));
         }
     }
+
+    if let Some(keyboard_layout) = args.keyboard_layout {
+        config.keyboard_layout = keyboard_layout;
+    }
+    if let Some(seed) = args.seed {
+        config.seed = Some(seed);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::Parser;
 
     use std::io::Write;
 
@@ -287,7 +866,7 @@ mod tests {
         let config = Config::default();
 
         assert_eq!(config.duration, Duration::from_secs(30));
-        assert_eq!(config.numbers, false);
+        assert!(!config.numbers);
         assert_eq!(config.numbers_ratio, 0.05);
     }
 
@@ -305,12 +884,45 @@ mod tests {
             config_path: None,
             save_results: None,
             results_path: None,
+            mode: None,
+            words: None,
+            quote_length: None,
+            quotes_path: None,
+            text: None,
+            keep_newlines: None,
+            code_language: None,
+            code_path: None,
+            skip_indentation: None,
+            keyboard_layout: None,
+            adaptive: None,
+            lesson_target_wpm: None,
+            lesson_target_accuracy: None,
+            seed: None,
+            language: None,
+            list_languages: false,
+            min_word_length: None,
+            max_word_length: None,
+            exclude_words: None,
+            generator: None,
+            corpus_path: None,
+            punctuation: None,
+            sentence_end_ratio: None,
+            comma_ratio: None,
+            apostrophe_ratio: None,
+            quote_ratio: None,
+            number_formats: None,
+            ending_symbols: None,
+            surrounding_symbols: None,
+            max_width: None,
+            live_stats: None,
+            focus: None,
+            type_to_start: None,
             history: None,
         };
         let config = Config::new(args, PathBuf::new()).expect("Unable to create config");
 
         assert_eq!(config.duration, Duration::from_secs(30));
-        assert_eq!(config.numbers, false);
+        assert!(!config.numbers);
         assert_eq!(config.numbers_ratio, 0.05);
     }
 
@@ -333,13 +945,46 @@ mod tests {
             config_path: None,
             save_results: None,
             results_path: None,
+            mode: None,
+            words: None,
+            quote_length: None,
+            quotes_path: None,
+            text: None,
+            keep_newlines: None,
+            code_language: None,
+            code_path: None,
+            skip_indentation: None,
+            keyboard_layout: None,
+            adaptive: None,
+            lesson_target_wpm: None,
+            lesson_target_accuracy: None,
+            seed: None,
+            language: None,
+            list_languages: false,
+            min_word_length: None,
+            max_word_length: None,
+            exclude_words: None,
+            generator: None,
+            corpus_path: None,
+            punctuation: None,
+            sentence_end_ratio: None,
+            comma_ratio: None,
+            apostrophe_ratio: None,
+            quote_ratio: None,
+            number_formats: None,
+            ending_symbols: None,
+            surrounding_symbols: None,
+            max_width: None,
+            live_stats: None,
+            focus: None,
+            type_to_start: None,
             history: None,
         };
         let config =
             Config::new(args, config_file.path().to_path_buf()).expect("Unable to create config");
 
         assert_eq!(config.duration, Duration::from_secs(10));
-        assert_eq!(config.numbers, true);
+        assert!(config.numbers);
         assert_eq!(config.numbers_ratio, 0.05);
     }
 
@@ -357,14 +1002,47 @@ mod tests {
             config_path: None,
             save_results: Some(false),
             results_path: None,
+            mode: None,
+            words: None,
+            quote_length: None,
+            quotes_path: None,
+            text: None,
+            keep_newlines: None,
+            code_language: None,
+            code_path: None,
+            skip_indentation: None,
+            keyboard_layout: None,
+            adaptive: None,
+            lesson_target_wpm: None,
+            lesson_target_accuracy: None,
+            seed: None,
+            language: None,
+            list_languages: false,
+            min_word_length: None,
+            max_word_length: None,
+            exclude_words: None,
+            generator: None,
+            corpus_path: None,
+            punctuation: None,
+            sentence_end_ratio: None,
+            comma_ratio: None,
+            apostrophe_ratio: None,
+            quote_ratio: None,
+            number_formats: None,
+            ending_symbols: None,
+            surrounding_symbols: None,
+            max_width: None,
+            live_stats: None,
+            focus: None,
+            type_to_start: None,
             history: None,
         };
         let config = Config::new(args, PathBuf::new()).expect("Unable to create config");
 
         assert_eq!(config.duration, Duration::from_secs(10));
-        assert_eq!(config.numbers, true);
+        assert!(config.numbers);
         assert_eq!(config.numbers_ratio, 0.05);
-        assert_eq!(config.save_results, false);
+        assert!(!config.save_results);
     }
 
     #[test]
@@ -386,19 +1064,160 @@ mod tests {
             save_results: Some(true),
             config_path: Some(String::from("/config.json")),
             results_path: Some(String::from("/some-path")),
+            mode: None,
+            words: None,
+            quote_length: None,
+            quotes_path: None,
+            text: None,
+            keep_newlines: None,
+            code_language: None,
+            code_path: None,
+            skip_indentation: None,
+            keyboard_layout: None,
+            adaptive: None,
+            lesson_target_wpm: None,
+            lesson_target_accuracy: None,
+            seed: None,
+            language: None,
+            list_languages: false,
+            min_word_length: None,
+            max_word_length: None,
+            exclude_words: None,
+            generator: None,
+            corpus_path: None,
+            punctuation: None,
+            sentence_end_ratio: None,
+            comma_ratio: None,
+            apostrophe_ratio: None,
+            quote_ratio: None,
+            number_formats: None,
+            ending_symbols: None,
+            surrounding_symbols: None,
+            max_width: None,
+            live_stats: None,
+            focus: None,
+            type_to_start: None,
             history: None,
         };
         let config =
             Config::new(args, config_file.path().to_path_buf()).expect("Unable to create config");
 
         assert_eq!(config.duration, Duration::from_secs(20));
-        assert_eq!(config.numbers, false);
+        assert!(!config.numbers);
         assert_eq!(config.numbers_ratio, 0.05);
         assert_eq!(
             config.dictionary_path,
             Some(PathBuf::from("/etc/dict/words"))
         );
-        assert_eq!(config.save_results, true);
+        assert!(config.save_results);
         assert_eq!(config.results_path, Some(PathBuf::from("/some-path")));
     }
+
+    #[test]
+    fn words_argument_should_switch_to_words_mode() {
+        let args = Args {
+            duration: None,
+            mode: None,
+            words: Some(25),
+            quote_length: None,
+            quotes_path: None,
+            text: None,
+            keep_newlines: None,
+            code_language: None,
+            code_path: None,
+            skip_indentation: None,
+            numbers: None,
+            numbers_ratio: None,
+            symbols: None,
+            symbols_ratio: None,
+            dictionary_path: None,
+            uppercase: None,
+            uppercase_ratio: None,
+            config_path: None,
+            save_results: None,
+            results_path: None,
+            keyboard_layout: None,
+            adaptive: None,
+            lesson_target_wpm: None,
+            lesson_target_accuracy: None,
+            seed: None,
+            language: None,
+            list_languages: false,
+            min_word_length: None,
+            max_word_length: None,
+            exclude_words: None,
+            generator: None,
+            corpus_path: None,
+            punctuation: None,
+            sentence_end_ratio: None,
+            comma_ratio: None,
+            apostrophe_ratio: None,
+            quote_ratio: None,
+            number_formats: None,
+            ending_symbols: None,
+            surrounding_symbols: None,
+            max_width: None,
+            live_stats: None,
+            focus: None,
+            type_to_start: None,
+            history: None,
+        };
+        let config = Config::new(args, PathBuf::new()).expect("Unable to create config");
+
+        assert_eq!(config.mode, TestMode::Words);
+        assert_eq!(config.words, 25);
+    }
+
+    #[test]
+    fn should_save_settings_keeping_other_options() {
+        let mut config_file = tempfile::NamedTempFile::new().expect("Unable to create temp file");
+        config_file
+            .write_all(
+                r#"{"dictionary_path": "/tmp/words", "colors": {"correct_match_fg": "green"}}"#
+                    .as_bytes(),
+            )
+            .expect("Unable to write to temp file");
+        let config = Config {
+            numbers: true,
+            mode: TestMode::Words,
+            config_file_path: config_file.path().to_path_buf(),
+            ..Config::default()
+        };
+
+        config.save_settings().expect("Unable to save settings");
+
+        let saved_config = fs::read_to_string(config_file.path()).expect("Unable to read file");
+        let saved_config: serde_json::Value =
+            serde_json::from_str(&saved_config).expect("Unable to parse config file");
+        assert_eq!(saved_config["numbers"], true);
+        assert_eq!(saved_config["mode"], "words");
+        assert_eq!(saved_config["duration"], 30);
+        assert_eq!(saved_config["colors"]["correct_match_fg"], "green");
+        assert!(saved_config.get("dictionary_path").is_none());
+    }
+
+    #[test]
+    fn should_parse_saved_language_with_the_documented_name() {
+        let mut config_file = tempfile::NamedTempFile::new().expect("Unable to create temp file");
+        config_file
+            .write_all(r#"{"language": "english_200"}"#.as_bytes())
+            .expect("Unable to write to temp file");
+        let config = Config::new(
+            Args::parse_from(["donkeytype"]),
+            config_file.path().to_path_buf(),
+        )
+        .expect("Unable to create config");
+        assert_eq!(config.language, Language::English200);
+
+        config.save_settings().expect("Unable to save settings");
+        let saved_config = fs::read_to_string(config_file.path()).expect("Unable to read file");
+        assert!(saved_config.contains(r#""language": "english_200""#));
+
+        let config = Config::new(
+            Args::parse_from(["donkeytype"]),
+            config_file.path().to_path_buf(),
+        )
+        .expect("Unable to create config");
+        assert_eq!(config.language, Language::English200);
+    }
 }
@@ -0,0 +1,63 @@
+//! module with text used to train the Markov chain generator when no corpus file is provided
+
+pub const CORPUS: &str = "The morning was quiet, and the river moved slowly past the old mill. \
+A small boat was tied to the wooden post near the bridge. \
+Nobody in the village could remember who had left it there. \
+The children liked to sit in the boat and pretend they were sailing to distant lands. \
+Every summer the water grew warm enough to swim, and the whole village came down to the shore. \
+The baker brought fresh bread, and the farmer brought apples from his orchard. \
+It was the kind of place where time seemed to move a little slower than anywhere else. \
+Have you ever walked through a forest after the rain? \
+The air smells of wet earth, and every leaf shines in the light. \
+Birds return to the branches and begin to sing again. \
+You can hear water dripping from the trees long after the clouds have gone. \
+Some people prefer the city, with its noise and lights and endless movement. \
+Others need the silence of the mountains to think clearly. \
+There is no right answer, because every person finds peace in a different place. \
+The old teacher told her students that curiosity was the most important thing they could have. \
+She said that a good question is worth more than a quick answer. \
+Her students did not always understand her, but they remembered her words for many years. \
+When the storm came, the lights went out across the whole town. \
+Families gathered around candles and told stories they had not told in years. \
+In the morning the streets were covered with branches, but the sky was clear and blue. \
+The train left the station exactly at noon. \
+A man in a grey coat was reading a newspaper by the window. \
+Next to him a young woman was writing in a small notebook. \
+Neither of them looked up when the conductor walked by. \
+Outside, the fields passed by in long green lines. \
+Learning to type quickly takes patience and practice. \
+At first your fingers feel slow, and every mistake seems to stop you. \
+After a few weeks you stop looking at the keys and start thinking about the words. \
+The best way to improve is to practise a little every day. \
+Speed will come naturally once your hands know where to go. \
+What makes a good story? \
+Some say it is the characters, others say it is the surprise at the end. \
+A story can be simple and still stay with you for the rest of your life. \
+The lighthouse stood alone on the rocks at the edge of the sea. \
+Its keeper climbed the narrow stairs every evening to light the lamp. \
+Ships passing in the dark trusted that light to guide them home. \
+One winter night the keeper saw a small boat struggling against the waves. \
+He ran down to the shore with a rope and waited until the boat came close. \
+The sailors were cold and tired, but they were safe. \
+The market opened early on Saturday mornings. \
+Merchants called out prices, and the smell of coffee filled the square. \
+A musician played the violin near the fountain while people dropped coins into his hat. \
+By the afternoon the stalls were empty and the square was quiet again. \
+Remember to take breaks, stretch your hands, and drink some water! \
+Good habits matter more than a single fast result. \
+The scientist looked at the results for a long time before she spoke. \
+Nothing in the data matched what they had expected to find. \
+It was either a mistake or the beginning of something important. \
+She decided to run the experiment again, this time more carefully. \
+The garden behind the house was full of roses and tall sunflowers. \
+Every evening the grandmother watered the plants and talked to them quietly. \
+She believed that flowers grew better when someone cared for them. \
+Perhaps she was right, because her garden was the most beautiful in the street. \
+The city library had more books than anyone could read in a lifetime. \
+On rainy days it was full of students, old men reading newspapers, and children looking for adventure stories. \
+The librarian knew every shelf and could find any book in a minute. \
+Why do we remember some days so clearly and forget others completely? \
+Maybe the mind keeps what it needs and lets the rest fade away. \
+The road through the valley was long and winding. \
+Travellers often stopped at the small inn halfway up the hill. \
+The owner served hot soup and listened to their stories late into the night.";
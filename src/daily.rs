@@ -0,0 +1,156 @@
+//! Module with the daily challenge run with `donkeytype daily`
+//!
+//! Daily challenge is the same test for everyone on a given calendar day.
+//! It's a `time` mode test with fixed duration and options, using the builtin English dictionary,
+//! and generated with a seed derived from the date.
+//! Display options, like colors or keyboard layout, are still taken from the user's config.
+//!
+//! Results of daily challenges are flagged in the results file,
+//! so the best score of the day and the streak of days with a daily challenge can be shown.
+
+use chrono::{Days, NaiveDate};
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::dictionary::Language;
+use crate::helpers::get_numeric_hash;
+use crate::markov_input::Generator;
+use crate::test_mode::TestMode;
+use crate::test_results::TestResults;
+
+/// Duration of the daily challenge
+const DAILY_DURATION: Duration = Duration::from_secs(30);
+
+/// Returns the seed of the daily challenge of given day
+pub fn get_daily_seed(date: NaiveDate) -> u64 {
+    get_numeric_hash(&format!("donkeytype-daily-{}", date.format("%Y-%m-%d")))
+}
+
+/// Overwrites options deciding about the content of the test with the ones of the daily challenge
+pub fn apply_daily_options(config: &mut Config, date: NaiveDate) {
+    config.mode = TestMode::Time;
+    config.duration = DAILY_DURATION;
+    config.numbers = false;
+    config.symbols = false;
+    config.uppercase = false;
+    config.punctuation = false;
+    config.dictionary_path = None;
+    config.language = Language::English;
+    config.min_word_length = None;
+    config.max_word_length = None;
+    config.exclude_words = vec![];
+    config.generator = Generator::Words;
+    config.adaptive = false;
+    config.seed = Some(get_daily_seed(date));
+    config.daily = true;
+}
+
+/// Describes today's best score and the streak of days with a daily challenge
+///
+/// `current_results` are the results of the daily challenge that has just finished.
+pub fn get_daily_summary(
+    previous_results: &[TestResults],
+    current_results: &TestResults,
+) -> String {
+    let today = current_results.local_datetime.date_naive();
+    let daily_results = previous_results
+        .iter()
+        .chain([current_results])
+        .filter(|results| results.daily == Some(true))
+        .collect::<Vec<_>>();
+
+    let todays_best_wpm = daily_results
+        .iter()
+        .filter(|results| results.local_datetime.date_naive() == today)
+        .filter_map(|results| results.wpm)
+        .fold(0.0, f64::max);
+
+    let days = daily_results
+        .iter()
+        .map(|results| results.local_datetime.date_naive())
+        .collect::<HashSet<_>>();
+    let mut streak = 0;
+    let mut day = Some(today);
+    while let Some(current_day) = day.filter(|day| days.contains(day)) {
+        streak += 1;
+        day = current_day.checked_sub_days(Days::new(1));
+    }
+
+    format!(
+        "Daily challenge {}: today's best {:.2} WPM, your daily streak {} day(s)",
+        today.format("%Y-%m-%d"),
+        todays_best_wpm,
+        streak
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeZone};
+
+    use super::*;
+    use crate::test_results::Stats;
+
+    fn get_daily_results(date: NaiveDate, wpm: f64) -> TestResults {
+        let mut results = TestResults::new(
+            Stats {
+                wpm,
+                ..Stats::default()
+            },
+            Config {
+                daily: true,
+                ..Config::default()
+            },
+            true,
+        );
+        results.local_datetime = Local
+            .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+            .unwrap();
+        results
+    }
+
+    #[test]
+    fn should_derive_same_options_from_the_same_date() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let mut config = Config {
+            duration: Duration::from_secs(120),
+            numbers: true,
+            ..Config::default()
+        };
+        apply_daily_options(&mut config, date);
+
+        assert_eq!(config.duration, DAILY_DURATION);
+        assert!(!config.numbers);
+        assert_eq!(config.seed, Some(get_daily_seed(date)));
+        assert_ne!(
+            get_daily_seed(date),
+            get_daily_seed(NaiveDate::from_ymd_opt(2024, 3, 2).unwrap())
+        );
+    }
+
+    #[test]
+    fn should_count_todays_best_and_streak() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let previous_results = vec![
+            get_daily_results(NaiveDate::from_ymd_opt(2024, 3, 7).unwrap(), 90.0),
+            get_daily_results(NaiveDate::from_ymd_opt(2024, 3, 9).unwrap(), 50.0),
+            get_daily_results(today, 60.0),
+            TestResults::new(
+                Stats {
+                    wpm: 100.0,
+                    ..Stats::default()
+                },
+                Config::default(),
+                true,
+            ),
+        ];
+
+        let summary = get_daily_summary(&previous_results, &get_daily_results(today, 55.0));
+
+        assert_eq!(
+            summary,
+            "Daily challenge 2024-03-10: today's best 60.00 WPM, your daily streak 2 day(s)"
+        );
+    }
+}
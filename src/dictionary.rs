@@ -1,6 +1,52 @@
-//! module with words used to generate expected input
+//! module with builtin words used to generate expected input
+//!
+//! Each language has its own list of common words, ordered from the most frequent ones.
+//! Language is chosen with the `language` option in config.
 
-pub const WORDS: &str = "the
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Enum used in config for choosing the builtin dictionary.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    /// 200 most common English words
+    #[value(name = "english_200")]
+    #[serde(rename = "english_200")]
+    English200,
+    /// 1000 most common English words
+    #[default]
+    English,
+    German,
+    Spanish,
+    French,
+    Polish,
+    Russian,
+}
+
+impl Language {
+    /// Returns words of the builtin dictionary of the language
+    pub fn get_words(&self) -> Vec<&'static str> {
+        match self {
+            Language::English200 => ENGLISH.lines().take(200).collect(),
+            Language::English => ENGLISH.lines().collect(),
+            Language::German => GERMAN.lines().collect(),
+            Language::Spanish => SPANISH.lines().collect(),
+            Language::French => FRENCH.lines().collect(),
+            Language::Polish => POLISH.lines().collect(),
+            Language::Russian => RUSSIAN.lines().collect(),
+        }
+    }
+
+    /// Returns the name of the language used in config and arguments
+    pub fn get_name(&self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+}
+
+const ENGLISH: &str = "the
 of
 to
 and
@@ -1000,3 +1046,1116 @@ quotient
 teeth
 shell
 neck";
+
+const GERMAN: &str = "der
+die
+und
+in
+den
+von
+zu
+das
+mit
+sich
+des
+auf
+für
+ist
+im
+dem
+nicht
+ein
+eine
+als
+auch
+es
+an
+werden
+aus
+er
+hat
+dass
+sie
+nach
+wird
+bei
+einer
+um
+am
+sind
+noch
+wie
+einem
+über
+einen
+so
+zum
+war
+haben
+nur
+oder
+aber
+vor
+zur
+bis
+mehr
+durch
+man
+sein
+wurde
+sei
+hatte
+kann
+gegen
+vom
+können
+schon
+wenn
+habe
+seine
+ihre
+dann
+unter
+wir
+soll
+ich
+eines
+Jahr
+zwei
+Jahre
+diese
+dieser
+wieder
+keine
+seiner
+worden
+will
+zwischen
+immer
+was
+sagte
+gibt
+alle
+diesem
+seit
+muss
+wurden
+beim
+doch
+jetzt
+waren
+drei
+neue
+damit
+bereits
+da
+ihr
+seinen
+müssen
+ab
+ihrer
+ob
+sondern
+heute
+nun
+weil
+ihm
+ersten
+dazu
+viel
+dort
+ohne
+wo
+etwa
+kein
+selbst
+sehr
+ganz
+hier
+eigentlich
+Stadt
+Land
+Zeit
+Mann
+Frau
+Kind
+Haus
+Tag
+Welt
+leben
+Hand
+Weg
+Arbeit
+Geld
+Wasser
+Schule
+Frage
+Name
+Seite
+Teil
+Recht
+Ende
+Grund
+Stunde
+Woche
+Monat
+Abend
+Morgen
+Nacht
+Freund
+Familie
+Auge
+Wort
+Stimme
+gut
+groß
+klein
+alt
+jung
+lang
+kurz
+hoch
+neu
+schnell
+langsam
+richtig
+falsch
+gehen
+kommen
+machen
+sagen
+sehen
+geben
+nehmen
+finden
+denken
+wissen
+stehen
+lassen
+liegen
+bleiben
+heißen
+halten
+zeigen
+führen
+sprechen
+bringen
+fahren
+spielen
+arbeiten
+brauchen
+folgen
+lernen
+verstehen
+setzen";
+
+const SPANISH: &str = "de
+la
+que
+el
+en
+y
+a
+los
+se
+del
+las
+un
+por
+con
+no
+una
+su
+para
+es
+al
+lo
+como
+más
+o
+pero
+sus
+le
+ha
+me
+si
+sin
+sobre
+este
+ya
+entre
+cuando
+todo
+esta
+ser
+son
+dos
+también
+fue
+había
+era
+muy
+años
+hasta
+desde
+está
+mi
+porque
+qué
+sólo
+han
+yo
+hay
+vez
+puede
+todos
+así
+nos
+ni
+parte
+tiene
+él
+uno
+donde
+bien
+tiempo
+mismo
+ese
+ahora
+cada
+e
+vida
+otro
+después
+te
+otros
+aunque
+esa
+eso
+hace
+otra
+gobierno
+tan
+durante
+siempre
+día
+tanto
+ella
+tres
+sí
+dijo
+sido
+gran
+país
+según
+menos
+mundo
+año
+antes
+estado
+contra
+sino
+forma
+caso
+nada
+hacer
+general
+estaba
+poco
+estos
+presidente
+mayor
+ante
+unos
+les
+algo
+hacia
+casa
+ellos
+ayer
+hecho
+primera
+mucho
+mientras
+además
+quien
+momento
+millones
+esto
+hombre
+están
+pues
+hoy
+lugar
+nacional
+trabajo
+otras
+mejor
+nuevo
+decir
+algunos
+entonces
+todas
+días
+debe
+política
+cómo
+casi
+toda
+tal
+luego
+pasado
+primer
+medio
+va
+estas
+sea
+tenía
+nunca
+poder
+aquí
+ver
+veces
+embargo
+partido
+personas
+grupo
+cuenta
+pueden
+tienen
+misma
+nueva
+cual
+fueron
+mujer
+frente
+tras
+cosas
+fin
+ciudad
+he
+social
+manera
+tener
+sistema
+será
+historia
+muchos
+tipo
+cuatro
+dentro
+nuestro
+punto
+dice
+ello
+cualquier
+noche
+aún
+agua
+parece
+haber
+situación
+fuera
+bajo
+grandes
+nuestra
+ejemplo
+acuerdo
+habían
+usted
+estados
+hizo
+nadie
+países
+horas
+posible
+tarde
+ley
+importante
+guerra
+desarrollo
+proceso
+realidad
+sentido
+lado
+mí
+tu
+cambio
+allí
+mano
+eran
+estar
+número
+sociedad
+unas
+centro
+padre
+gente
+final
+relación
+cuerpo
+obra
+incluso
+través
+último
+madre
+mis
+modo
+problema
+cinco
+hombres
+información
+ojos
+muerte
+nombre
+algunas
+público
+mujeres
+siglo
+todavía
+meses
+mañana
+esos
+nosotros
+hora
+muchas
+pueblo
+alguna
+dar
+problemas
+don
+da
+tú
+derecho
+verdad
+unidos
+podría
+sería
+junto
+cabeza
+aquel
+cuanto
+tierra
+equipo
+segundo
+director
+dicho
+cierto
+casos
+manos
+nivel
+podía
+familia
+largo
+partir
+falta
+llegar
+propio
+ministro
+cosa
+primero
+seguridad
+hemos
+mal
+trata
+algún
+tuvo
+respecto
+semana
+varios
+real
+sé
+voz
+paso";
+
+const FRENCH: &str = "de
+la
+le
+et
+les
+des
+en
+un
+du
+une
+que
+est
+pour
+qui
+dans
+a
+par
+plus
+pas
+au
+sur
+ne
+se
+ce
+il
+sont
+avec
+ou
+son
+aux
+d'un
+cette
+d'une
+ont
+ses
+mais
+comme
+on
+tout
+nous
+sa
+été
+fait
+elle
+leur
+peut
+même
+deux
+bien
+ces
+faire
+entre
+dont
+était
+sans
+aussi
+lui
+être
+ans
+depuis
+après
+si
+elles
+sous
+moins
+avait
+alors
+temps
+leurs
+très
+autres
+autre
+encore
+avant
+tous
+notre
+non
+où
+premier
+pays
+contre
+vous
+peu
+donc
+ainsi
+année
+ils
+je
+toute
+nouveau
+fois
+grand
+toujours
+déjà
+jour
+partie
+aujourd'hui
+trois
+place
+moment
+part
+homme
+chose
+monde
+vie
+main
+femme
+enfant
+maison
+ville
+travail
+yeux
+nuit
+tête
+père
+mère
+mot
+porte
+côté
+fin
+eau
+terre
+ami
+point
+chez
+rien
+jamais
+voir
+dire
+aller
+venir
+savoir
+pouvoir
+vouloir
+prendre
+donner
+trouver
+parler
+mettre
+passer
+croire
+devoir
+tenir
+rester
+penser
+regarder
+sembler
+laisser
+porter
+entendre
+rendre
+comprendre
+connaître
+arriver
+vivre
+attendre
+sortir
+écrire
+lire
+suivre
+ouvrir
+jouer
+petit
+bon
+vieux
+beau
+jeune
+long
+haut
+seul
+dernier
+propre
+blanc
+noir
+rouge
+vrai
+simple
+possible
+beaucoup
+ici
+là
+maintenant
+demain
+hier
+tard
+tôt
+vite
+souvent
+parfois
+enfin
+pourquoi
+comment
+quand
+quoi
+quel
+chaque
+plusieurs
+quelque
+personne";
+
+const POLISH: &str = "i
+w
+nie
+na
+z
+się
+do
+to
+że
+a
+o
+jak
+ale
+po
+co
+tak
+za
+od
+jest
+jego
+go
+już
+tylko
+mnie
+jej
+ten
+przez
+czy
+być
+było
+może
+ja
+ty
+on
+ona
+my
+wy
+oni
+by
+tego
+był
+bardzo
+jeszcze
+jednak
+przy
+gdy
+kiedy
+też
+tym
+teraz
+nawet
+gdzie
+aby
+więc
+tam
+lub
+bez
+który
+która
+które
+pod
+tu
+dla
+nad
+przed
+między
+u
+mi
+ci
+mu
+im
+nas
+was
+ich
+coś
+nic
+wszystko
+każdy
+cały
+inny
+sam
+taki
+dobry
+dobrze
+nowy
+stary
+duży
+mały
+wielki
+długi
+krótki
+pierwszy
+ostatni
+dzień
+rok
+czas
+życie
+człowiek
+ludzie
+dom
+ręka
+oko
+głowa
+świat
+miasto
+kraj
+praca
+woda
+ziemia
+droga
+słowo
+rzecz
+strona
+sprawa
+prawo
+pieniądze
+matka
+ojciec
+syn
+córka
+brat
+siostra
+dziecko
+kobieta
+mężczyzna
+przyjaciel
+noc
+rano
+wieczór
+tydzień
+miesiąc
+godzina
+chwila
+szkoła
+pokój
+drzwi
+okno
+stół
+miejsce
+koniec
+początek
+pytanie
+odpowiedź
+mieć
+robić
+mówić
+wiedzieć
+chcieć
+móc
+musieć
+iść
+przyjść
+widzieć
+dać
+wziąć
+myśleć
+patrzeć
+słyszeć
+znać
+żyć
+pracować
+czekać
+pisać
+czytać
+grać
+siedzieć
+stać
+leżeć
+spać
+jeść
+pić
+kochać
+lubić
+pamiętać
+zrobić
+powiedzieć
+zobaczyć
+wrócić
+zostać
+trzeba
+zawsze
+nigdy
+często
+dziś
+jutro
+wczoraj
+dlaczego
+dlatego
+potem
+razem
+prawie
+bardziej
+mniej
+więcej
+dość
+trochę
+wiele
+kilka
+dwa
+trzy
+cztery
+pięć
+sto
+tysiąc";
+
+const RUSSIAN: &str = "и
+в
+не
+на
+я
+быть
+он
+с
+что
+а
+по
+это
+она
+этот
+к
+но
+они
+мы
+как
+из
+у
+который
+то
+за
+свой
+весь
+год
+от
+так
+о
+для
+ты
+же
+все
+тот
+мочь
+вы
+человек
+такой
+его
+сказать
+только
+или
+ещё
+бы
+себя
+один
+уже
+до
+время
+если
+сам
+когда
+другой
+вот
+говорить
+наш
+мой
+знать
+стать
+при
+чтобы
+дело
+жизнь
+кто
+первый
+очень
+два
+день
+её
+новый
+рука
+даже
+во
+со
+раз
+где
+там
+под
+можно
+ну
+какой
+после
+их
+работа
+без
+самый
+потом
+надо
+хотеть
+ли
+слово
+идти
+большой
+должен
+место
+иметь
+ничто
+сейчас
+тут
+лицо
+каждый
+друг
+нет
+теперь
+ни
+глаз
+тоже
+тогда
+видеть
+вопрос
+через
+да
+здесь
+дом
+сторона
+какой-то
+думать
+сделать
+страна
+жить
+чем
+мир
+об
+последний
+случай
+голова
+более
+делать
+что-то
+смотреть
+ребёнок
+просто
+конечно
+сила
+российский
+конец
+перед
+несколько
+вид
+система
+всегда
+работать
+между
+три
+понять
+пойти
+часть
+спросить
+город
+дать
+также
+никто
+понимать
+получить
+отношение
+лишь
+второй
+именно
+значит
+хорошо
+дверь
+почему
+война
+машина
+земля";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_have_words_in_every_language() {
+        assert_eq!(Language::English200.get_words().len(), 200);
+        assert_eq!(Language::English.get_words().len(), 1000);
+        assert_eq!(Language::English200.get_name(), "english_200");
+
+        for language in Language::value_variants() {
+            let words = language.get_words();
+            assert!(words.len() >= 100);
+            assert!(words
+                .iter()
+                .all(|word| !word.is_empty() && !word.contains(' ')));
+        }
+    }
+}
@@ -1,41 +1,109 @@
 //! Module creating the expected input for the test runner
 //!
-//! It reads dictionary file to get list of words
-//! then shuffles this list
-//! optionally replaces words with numbers if flag `numbers` is set to true in config
-//! and returns as a string
+//! It reads dictionary file, or takes builtin dictionary of the `language` from config,
+//! to get list of words
+//! then skips words filtered out by `min_word_length`, `max_word_length` and `exclude_words`
+//! then samples random words from this list, more frequent words are sampled more often
+//! optionally replaces words with numbers if flag `numbers` is set to true in config,
+//! following random `number_formats` from config if there are any
+//! optionally adds `ending_symbols` or `surrounding_symbols` if flag `symbols` is set to true
+//! optionally builds sentences from words if flag `punctuation` is set to true in config
+//! and returns them as a string.
+//!
+//! Words are sampled one by one when the runner asks for characters which weren't generated yet,
+//! so the expected input is an endless stream that never repeats, and generated characters
+//! are cached, so accessing the part being typed or rendered is cheap even in long tests.
+//!
+//! All random choices are made with a generator seeded with `seed` from config,
+//! so the same seed and config always give the same expected input.
+//!
+//! In `adaptive` mode words containing letters and bigrams the user is weak on are sampled more
+//! often.
 //!
 //! Dictionary file should be a text file in format of single words per line.
+//! Each word can be followed by a tab and its frequency (`word<TAB>count`).
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use mockall::automock;
-use rand::{seq::SliceRandom, thread_rng, Rng};
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    rngs::StdRng,
+    seq::SliceRandom,
+    Rng,
+};
 use std::io::Read;
 
 use crate::config::Config;
-use crate::dictionary;
-use crate::helpers::split_by_char_index;
+use crate::helpers::{get_chars_range, get_rng};
+use crate::weak_keys::WeakKeys;
+
+/// Minimal amount of words in a sentence in punctuation mode
+const MIN_SENTENCE_WORDS_COUNT: usize = 3;
 
-/// Struct used by runner to hold generate the text used for validation and as a placeholder
+/// Maximal amount of words in a span surrounded by quotes in punctuation mode
+const MAX_QUOTE_WORDS_COUNT: usize = 4;
+
+/// Ratios of sentences ending with a question mark and with an exclamation mark
+const QUESTION_RATIO: f64 = 0.15;
+const EXCLAMATION_RATIO: f64 = 0.1;
+
+/// Position of the generated word in its sentence, used in punctuation mode
+#[derive(Debug, Default)]
+struct SentenceState {
+    words_count: usize,
+    /// amount of words left until the quote is closed, if a quote is open
+    quote_words_left: Option<usize>,
+}
+
+/// Symbols appended to the end of a word, used if there are none in config
+pub const DEFAULT_ENDING_SYMBOLS: [&str; 4] = [".", ",", "!", "?"];
+
+/// Pairs of symbols surrounding a word, used if there are none in config
+pub const DEFAULT_SURROUNDING_SYMBOLS: [&str; 5] = ["[]", "{}", "()", "\"\"", "''"];
+
+/// Character of number format replaced with a random digit
+const DIGIT_PLACEHOLDER: char = '#';
+
+/// Struct used by runner to generate the text used for validation and as a placeholder
+///
+/// Words are sampled lazily, only when runner asks for characters that weren't generated yet,
+/// and generated characters are cached, so the text never changes nor repeats.
 #[derive(Debug)]
 pub struct ExpectedInput {
-    str: String,
+    config: Config,
+    words: Vec<String>,
+    weights: WeightedIndex<f64>,
+    rng: StdRng,
+    surrounding_symbols: Vec<(String, String)>,
+    sentence: SentenceState,
+    chars: Vec<char>,
 }
 
 impl ExpectedInput {
     /// Create new struct instance by reading the dictionary file
     ///
-    /// After reading the file iterate over the words and apply the
-    /// specified settings.
+    /// After reading the file words are sampled from it and the
+    /// specified settings are applied to each of them.
     ///
     /// Each setting is applied according to the specified ratio.
     /// * `uppercase` will capitalize the word. ("hello" => "Hello")
-    /// * `numbers` will turn each letter of a word into a random number. (
-    /// "hello" => "52139")
-    /// * `symbols` will either append a symbol or surround the word with
-    /// matching symbols. ("hello" => "hello!", "hello" => "{hello}")
+    /// * `numbers` will turn each letter of a word into a random number,
+    ///   or fill random format from `number_formats` with random digits.
+    ///   ("hello" => "52139", "19##" => "1987")
+    /// * `symbols` will either append one of `ending_symbols` or surround the word with
+    ///   one of `surrounding_symbols`. ("hello" => "hello!", "hello" => "{hello}")
     pub fn new(config: &Config) -> Result<Self, anyhow::Error> {
-        let mut str = dictionary::WORDS.to_string();
+        Self::new_with_weak_keys(config, &WeakKeys::default())
+    }
+
+    /// Create new struct instance in which words containing `weak_keys` are sampled more often
+    ///
+    /// Without any weak keys it's the same as `new`.
+    pub fn new_with_weak_keys(
+        config: &Config,
+        weak_keys: &WeakKeys,
+    ) -> Result<Self, anyhow::Error> {
+        let mut str = config.language.get_words().join("\n");
         if let Some(dictionary_path) = &config.dictionary_path {
             str = String::from("");
             let mut file =
@@ -44,77 +112,239 @@ impl ExpectedInput {
                 .context("Unable to read dictionary file")?;
         }
 
-        let ending_symbols = ['.', ',', '!', '?'];
-        let surrounding_symbols = ['[', ']', '{', '}', '(', ')', '"', '"', '\'', '\''];
+        let dictionary = parse_dictionary(&str)?
+            .into_iter()
+            .filter(|(word, _)| is_word_allowed(word, config))
+            .collect::<Vec<_>>();
+        if dictionary.is_empty() {
+            bail!("No words left in the dictionary after applying word length filters and excluded words");
+        }
+        let weights = WeightedIndex::new(
+            dictionary
+                .iter()
+                .map(|(word, count)| count * weak_keys.get_word_weight(word)),
+        )
+        .context("Unable to sample words from the dictionary")?;
 
-        let mut rng = thread_rng();
-        let mut str: Vec<String> = str
-            .split("\n")
-            .map(|word| {
-                let mut word = word.to_string();
+        let surrounding_symbols = config
+            .surrounding_symbols
+            .iter()
+            .map(|symbols| split_symbols_pair(symbols))
+            .collect::<Result<Vec<_>>>()?;
 
-                // uppercase
-                if config.uppercase && rng.gen::<f64>() < config.uppercase_ratio {
-                    let mut c = word.chars();
-                    word = match c.next() {
-                        None => String::new(),
-                        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
-                    };
-                }
+        Ok(Self {
+            config: config.clone(),
+            words: dictionary
+                .into_iter()
+                .map(|(word, _)| word.to_string())
+                .collect(),
+            weights,
+            rng: get_rng(config.seed),
+            surrounding_symbols,
+            sentence: SentenceState::default(),
+            chars: vec![],
+        })
+    }
 
-                // numbers
-                if config.numbers && rng.gen::<f64>() < config.numbers_ratio {
-                    word = (0..word.len())
-                        .map(|_| rng.gen_range(b'0'..=b'9') as char)
-                        .collect();
-                }
+    /// Samples next word, applies settings to it and appends it to generated characters
+    fn push_word(&mut self) {
+        let config = &self.config;
+        let rng = &mut self.rng;
+
+        let index = self.weights.sample(rng);
+        let mut word = self.words[index].clone();
 
-                // symbols
-                if config.symbols && rng.gen::<f64>() < config.symbols_ratio {
-                    word = match rng.gen::<usize>() % 2 {
-                        0 => {
-                            let index = rng.gen::<usize>() % ending_symbols.len();
-                            format!("{}{}", word, ending_symbols[index])
-                        }
-                        1 => {
-                            let index = (rng.gen::<usize>() % (surrounding_symbols.len() / 2)) * 2;
-                            format!(
-                                "{}{}{}",
-                                surrounding_symbols[index],
-                                word,
-                                surrounding_symbols[index + 1]
-                            )
-                        }
-                        _ => word.to_string(),
+        // uppercase
+        if config.uppercase && rng.gen::<f64>() < config.uppercase_ratio {
+            let mut c = word.chars();
+            word = match c.next() {
+                None => String::new(),
+                Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+            };
+        }
+
+        // numbers
+        if config.numbers && rng.gen::<f64>() < config.numbers_ratio {
+            word = match config.number_formats.choose(rng) {
+                Some(number_format) => number_format
+                    .chars()
+                    .map(|char| match char {
+                        DIGIT_PLACEHOLDER => rng.gen_range(b'0'..=b'9') as char,
+                        _ => char,
+                    })
+                    .collect(),
+                None => (0..word.chars().count())
+                    .map(|_| rng.gen_range(b'0'..=b'9') as char)
+                    .collect(),
+            };
+        }
+
+        // symbols
+        if config.symbols && rng.gen::<f64>() < config.symbols_ratio {
+            let ending_symbols_count = config.ending_symbols.len();
+            let symbols_count = ending_symbols_count + self.surrounding_symbols.len();
+            if symbols_count > 0 {
+                let index = rng.gen_range(0..symbols_count);
+                word = match config.ending_symbols.get(index) {
+                    Some(ending_symbol) => format!("{}{}", word, ending_symbol),
+                    None => {
+                        let (opening, closing) =
+                            &self.surrounding_symbols[index - ending_symbols_count];
+                        format!("{}{}{}", opening, word, closing)
                     }
+                };
+            }
+        }
+
+        if config.punctuation {
+            word = self.punctuate(word);
+        }
+
+        if !self.chars.is_empty() {
+            self.chars.push(' ');
+        }
+        self.chars.extend(word.chars());
+    }
+
+    /// Makes the word a part of a sentence
+    ///
+    /// First word of a sentence is capitalized. Each word, after at least
+    /// `MIN_SENTENCE_WORDS_COUNT` words of the sentence, can end the sentence with `.`, `?` or `!`,
+    /// otherwise it can be followed by a comma.
+    /// Word can also get an apostrophe (`"dog" => "dog's"`) or open a quote spanning a few words,
+    /// which is closed at the end of the sentence at the latest.
+    fn punctuate(&mut self, word: String) -> String {
+        let config = &self.config;
+        let rng = &mut self.rng;
+        let sentence = &mut self.sentence;
+        let mut word = word;
+
+        if rng.gen::<f64>() < config.apostrophe_ratio && word.ends_with(char::is_alphabetic) {
+            word.push_str("'s");
+        }
+
+        if sentence.words_count == 0 {
+            let mut chars = word.chars();
+            word = match chars.next() {
+                None => String::new(),
+                Some(f) => f.to_uppercase().collect::<String>() + chars.as_str(),
+            };
+        }
+        sentence.words_count += 1;
+
+        let is_quote_started =
+            sentence.quote_words_left.is_none() && rng.gen::<f64>() < config.quote_ratio;
+        if is_quote_started {
+            word = format!("\"{}", word);
+            sentence.quote_words_left = Some(rng.gen_range(1..MAX_QUOTE_WORDS_COUNT));
+        }
+
+        // quote spans at least two words, so it's not ended together with the sentence
+        let is_sentence_end = !is_quote_started
+            && sentence.words_count >= MIN_SENTENCE_WORDS_COUNT
+            && rng.gen::<f64>() < config.sentence_end_ratio;
+        if is_sentence_end {
+            word.push(match rng.gen::<f64>() {
+                ratio if ratio < QUESTION_RATIO => '?',
+                ratio if ratio < QUESTION_RATIO + EXCLAMATION_RATIO => '!',
+                _ => '.',
+            });
+        } else if rng.gen::<f64>() < config.comma_ratio {
+            word.push(',');
+        }
+
+        if !is_quote_started {
+            sentence.quote_words_left = match sentence.quote_words_left {
+                Some(words_left) if words_left > 1 && !is_sentence_end => Some(words_left - 1),
+                Some(_) => {
+                    word.push('"');
+                    None
                 }
-                word
-            })
-            .collect::<Vec<_>>();
-        str.shuffle(&mut rng);
-        let str = str.join(" ").trim().to_string();
+                None => None,
+            };
+        }
+        if is_sentence_end {
+            *sentence = SentenceState::default();
+        }
 
-        Ok(Self { str })
+        word
     }
 }
 
+/// Parses dictionary with a word in each line, optionally followed by a tab and its frequency
+///
+/// Words without frequency have frequency of `1`.
+fn parse_dictionary(str: &str) -> Result<Vec<(&str, f64)>> {
+    str.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once('\t') {
+            Some((word, count)) => {
+                let count = count
+                    .trim()
+                    .parse::<f64>()
+                    .with_context(|| format!("Invalid frequency of word \"{}\"", word))?;
+                Ok((word.trim(), count))
+            }
+            None => Ok((line, 1.0)),
+        })
+        .filter(|word| !matches!(word, Ok((_, count)) if *count <= 0.0))
+        .collect()
+}
+
+/// Checks if the word passes length filters and isn't excluded in config
+fn is_word_allowed(word: &str, config: &Config) -> bool {
+    let len = word.chars().count();
+
+    config.min_word_length.is_none_or(|min| len >= min)
+        && config.max_word_length.is_none_or(|max| len <= max)
+        && !config
+            .exclude_words
+            .iter()
+            .any(|excluded_word| excluded_word.to_lowercase() == word.to_lowercase())
+}
+
+/// Splits surrounding symbols into opening and closing symbols of the same length
+///
+/// For example `"<<>>"` is split into `"<<"` and `">>"`.
+fn split_symbols_pair(symbols: &str) -> Result<(String, String)> {
+    let chars = symbols.chars().collect::<Vec<char>>();
+    if chars.is_empty() || chars.len() % 2 != 0 {
+        bail!(
+            "Surrounding symbols \"{}\" should have even, non-zero amount of characters",
+            symbols
+        );
+    }
+
+    let (opening, closing) = chars.split_at(chars.len() / 2);
+    Ok((opening.iter().collect(), closing.iter().collect()))
+}
+
 /// extracted to trait to create mock with `mockall` crate
 #[automock]
 pub trait ExpectedInputInterface {
-    fn get_string(&self, len: usize) -> String;
+    /// Returns characters of expected input from `start` index up to `end` index
+    ///
+    /// Returned string is shorter if expected input ends before `end`.
+    fn get_range(&mut self, start: usize, end: usize) -> String;
+    fn get_len(&self) -> Option<usize>;
 }
 
 impl ExpectedInputInterface for ExpectedInput {
-    /// Cuts string saved in ExpectedInput at specified length instance and returns it
+    /// Returns generated characters from the given range
     ///
-    /// If string is shorter than the specified length it repeats it enough times for it to be long
-    /// enough.
-    fn get_string(&self, len: usize) -> String {
-        let s = self.str.clone() + " ";
-        let s = s.repeat((len / s.chars().count()) as usize + 1);
-        let (s, _) = split_by_char_index(&s, len);
+    /// If not enough characters were generated yet, more words are sampled and cached.
+    fn get_range(&mut self, start: usize, end: usize) -> String {
+        while self.chars.len() < end {
+            self.push_word();
+        }
+
+        get_chars_range(&self.chars, start, end)
+    }
 
-        s.to_string()
+    /// Words are sampled endlessly so there is no end of the expected input
+    fn get_len(&self) -> Option<usize> {
+        None
     }
 }
 
@@ -124,54 +354,216 @@ mod tests {
 
     use super::*;
 
+    fn get_config(dictionary: &str) -> (Config, tempfile::NamedTempFile) {
+        let mut dictionary_file =
+            tempfile::NamedTempFile::new().expect("Unable to create temp file");
+        dictionary_file
+            .write_all(dictionary.as_bytes())
+            .expect("Unable to write to temp file");
+
+        (
+            Config {
+                dictionary_path: Some(dictionary_file.path().to_path_buf()),
+                ..Config::default()
+            },
+            dictionary_file, // It keeps tmp file while test is running
+        )
+    }
+
     #[test]
     fn new_expected_input_should_correctly_convert_to_str() {
         let config = Config::default();
-        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
 
-        assert_eq!(expected_input.get_string(12).chars().count(), 12);
+        assert_eq!(expected_input.get_range(0, 12).chars().count(), 12);
     }
 
     #[test]
     fn should_read_file() {
-        let mut config_file = tempfile::NamedTempFile::new().expect("Unable to create temp file");
-        config_file
-            .write_all(r#"halo"#.as_bytes())
-            .expect("Unable to write to temp file");
+        let (config, _dictionary_file) = get_config("halo");
+
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+
+        assert_eq!(expected_input.get_range(0, 4), "halo");
+    }
+
+    #[test]
+    fn should_generate_same_input_for_same_seed() {
         let config = Config {
-            dictionary_path: Some(config_file.path().to_path_buf()),
+            seed: Some(42),
+            numbers: true,
+            symbols: true,
+            uppercase: true,
             ..Config::default()
         };
 
-        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+        let mut same_seed_expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+        let mut other_seed_expected_input = ExpectedInput::new(&Config {
+            seed: Some(43),
+            ..config
+        })
+        .expect("unable to create expected input");
 
-        assert_eq!(expected_input.get_string(4), "halo");
+        assert_eq!(
+            expected_input.get_range(0, 1000),
+            same_seed_expected_input.get_range(0, 1000)
+        );
+        assert_ne!(
+            expected_input.get_range(0, 1000),
+            other_seed_expected_input.get_range(0, 1000)
+        );
     }
 
     #[test]
-    fn should_trim_string_to_match_len() {
-        let expected_input = ExpectedInput {
-            str: "abcdef".to_string(),
-        };
+    fn should_sample_words_by_frequency() {
+        let (mut config, _dictionary_file) = get_config("common\t1000\nrare\t10\nnever\t0\n");
+        config.seed = Some(42);
 
-        assert_eq!(expected_input.get_string(3), "abc");
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+        let str = expected_input.get_range(0, 100000);
+        let words = str.split(' ').collect::<Vec<_>>();
+        let count = |expected: &str| words.iter().filter(|word| **word == expected).count();
+
+        assert_eq!(count("never"), 0);
+        // common words are sampled about a hundred times more often than rare ones
+        let ratio = count("common") as f64 / count("rare") as f64;
+        assert!((50.0..200.0).contains(&ratio), "ratio was {}", ratio);
+        assert!(parse_dictionary("word\tmany").is_err());
     }
 
     #[test]
-    fn should_repeat_string_if_len_is_too_big() {
-        let expected_input = ExpectedInput {
-            str: "abc".to_string(),
+    fn should_filter_words_by_length_and_excluded_words() {
+        let config = Config {
+            min_word_length: Some(3),
+            max_word_length: Some(5),
+            exclude_words: vec!["The".to_string(), "which".to_string()],
+            ..Config::default()
         };
 
-        assert_eq!(expected_input.get_string(11), "abc abc abc");
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+
+        let str = expected_input.get_range(0, 10000);
+        // the last word can be cut in half
+        let (str, _) = str.rsplit_once(' ').expect("Unable to find words");
+
+        assert!(str.split(' ').all(|word| {
+            (3..=5).contains(&word.chars().count()) && word != "the" && word != "which"
+        }));
+        assert!(ExpectedInput::new(&Config {
+            min_word_length: Some(100),
+            ..Config::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn should_build_sentences_in_punctuation_mode() {
+        let (mut config, _dictionary_file) = get_config("foo\nbar\nbaz");
+        config.punctuation = true;
+        config.sentence_end_ratio = 0.3;
+        config.quote_ratio = 0.2;
+        config.apostrophe_ratio = 0.0;
+        config.seed = Some(42);
+
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+        let str = expected_input.get_range(0, 10000);
+        // the last word can be cut in half
+        let (str, _) = str.rsplit_once(' ').expect("Unable to find words");
+        let words = str.split(' ').collect::<Vec<_>>();
+
+        assert!(str.trim_start_matches('"').starts_with(char::is_uppercase));
+        for pair in words.windows(2) {
+            let next_word = pair[1].trim_start_matches('"');
+            let is_sentence_end = pair[0].trim_end_matches('"').ends_with(['.', '?', '!']);
+            assert_eq!(next_word.starts_with(char::is_uppercase), is_sentence_end);
+        }
+        // quotes are closed at the end of the sentence at the latest
+        let mut quotes_count = 0;
+        for word in &words {
+            quotes_count += word.matches('"').count();
+            if word.trim_end_matches('"').ends_with(['.', '?', '!']) {
+                assert_eq!(quotes_count % 2, 0, "{}", word);
+            }
+        }
+    }
+
+    #[test]
+    fn should_use_configured_number_formats_and_symbols() {
+        let (mut config, _dictionary_file) = get_config("foo\nbar");
+        config.numbers = true;
+        config.numbers_ratio = 0.5;
+        config.number_formats = vec!["19##".to_string(), "#.##".to_string()];
+        config.symbols = true;
+        config.symbols_ratio = 0.5;
+        config.ending_symbols = vec!["->".to_string()];
+        config.surrounding_symbols = vec!["<<>>".to_string()];
+
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+        let str = expected_input.get_range(0, 10000);
+        // the last word can be cut in half
+        let (str, _) = str.rsplit_once(' ').expect("Unable to find words");
+
+        for word in str.split(' ') {
+            let word = match word.strip_prefix("<<") {
+                Some(word) => word
+                    .strip_suffix(">>")
+                    .expect("Unable to find closing symbols"),
+                None => word.strip_suffix("->").unwrap_or(word),
+            };
+            let is_number = |format: &str| {
+                word.chars().count() == format.chars().count()
+                    && word.chars().zip(format.chars()).all(|(char, format_char)| {
+                        format_char == char || format_char == '#' && char.is_ascii_digit()
+                    })
+            };
+            assert!(
+                ["foo", "bar"].contains(&word) || is_number("19##") || is_number("#.##"),
+                "{}",
+                word
+            );
+        }
+        assert!(str.contains("->") && str.contains("<<") && str.contains('.'));
+
+        config.surrounding_symbols = vec!["<>>".to_string()];
+        assert!(ExpectedInput::new(&config).is_err());
+    }
+
+    #[test]
+    fn should_trim_string_to_match_len() {
+        let (config, _dictionary_file) = get_config("abcdef");
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+
+        assert_eq!(expected_input.get_range(0, 3), "abc");
+    }
+
+    #[test]
+    fn should_generate_more_words_if_range_is_too_big() {
+        let (config, _dictionary_file) = get_config("abc");
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
+
+        assert_eq!(expected_input.get_range(0, 3), "abc");
+        assert_eq!(expected_input.get_range(4, 11), "abc abc");
+        assert_eq!(expected_input.chars.len(), 11);
+        assert_eq!(expected_input.get_range(0, 11), "abc abc abc");
     }
 
     #[test]
     fn should_work_with_non_ascii_chars() {
-        let expected_input = ExpectedInput {
-            str: "Բարեւ Ձեզ".to_string(),
-        };
+        let (config, _dictionary_file) = get_config("Բարեւ");
+        let mut expected_input =
+            ExpectedInput::new(&config).expect("unable to create expected input");
 
-        assert_eq!(expected_input.get_string(5), "Բարեւ");
+        assert_eq!(expected_input.get_range(0, 5), "Բարեւ");
     }
 }
@@ -4,6 +4,8 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use crate::config::Config;
+use crate::keybindings::Action;
 use crate::runner::FrameWrapperInterface;
 
 pub struct HelpWindow;
@@ -13,7 +15,8 @@ impl HelpWindow {
         HelpWindow
     }
 
-    pub fn render(&self, frame: &mut impl FrameWrapperInterface) {
+    /// Renders the help window, listing the keys currently bound to each action
+    pub fn render(&self, frame: &mut impl FrameWrapperInterface, config: &Config) {
         let frame_rect = frame.area();
 
         if frame_rect.height < 3 {
@@ -21,31 +24,65 @@ impl HelpWindow {
             return;
         }
 
-        let help_text = vec![
-            "",
-            " Navigation:",
-            " 's'   - Start/resume the test",
-            " <Esc> - Pause the test",
-            " 'q'   - Quit",
-            " '?'   - Toggle this window",
-            "",
-            " Configuration:",
-            " --duration <seconds> - Set test duration",
-            " --numbers - Include numbers in the test",
-            " --uppercase - Include uppercase letters",
-            "",
-            " Run 'donkeytype help' in your terminal to get more information ",
-            "",
-        ];
-
-        let longest_help_msg_len = help_text.iter().map(|s| s.len()).max().unwrap();
+        let start_description = match config.type_to_start {
+            true => "Resume the test, typing the first character starts it",
+            false => "Start/resume the test",
+        };
+        let keybindings = &config.keybindings;
+        let navigation = [
+            (Action::Start, start_description),
+            (Action::Pause, "Pause the test"),
+            (Action::Restart, "Restart the test with a new text"),
+            (Action::WordDelete, "Remove the last word"),
+            (Action::Finish, "Finish the test in zen mode"),
+            (Action::Settings, "Change options of the test in settings"),
+            (Action::Quit, "Quit"),
+            (Action::Help, "Toggle this window"),
+        ]
+        .map(|(action, description)| (keybindings.describe_all(action), description));
+        let keys_width = navigation
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut help_text = vec![String::new(), " Navigation:".to_string()];
+        help_text.extend(
+            navigation
+                .iter()
+                .map(|(keys, description)| format!(" {:<keys_width$} - {}", keys, description)),
+        );
+        help_text.extend(
+            [
+                "",
+                " Configuration:",
+                " --duration <seconds> - Set test duration",
+                " --words <count> - Type given amount of words instead",
+                " --mode quote - Type a quote instead",
+                " --text <path> - Type your own text instead",
+                " --mode code - Type a code snippet instead",
+                " --mode zen - Type freely without expected input",
+                " --adaptive true - Practise letters you are weak on",
+                " --mode lesson - Learn letters one by one",
+                " --numbers - Include numbers in the test",
+                " --uppercase - Include uppercase letters",
+                "",
+                " Run 'donkeytype help' in your terminal to get more information ",
+                "",
+            ]
+            .map(String::from),
+        );
+
+        let longest_help_msg_len = help_text.iter().map(|s| s.chars().count()).max().unwrap();
         let help_text_lines_count = help_text.len();
 
         // check if there is enough space vertically to display the help message
         if frame_rect.height <= help_text_lines_count as u16 {
-            let paragraph =
-                Paragraph::new( "Terminal window is too short to display the help window\nresize the terminal or press \"?\" to return to the test")
-                .style(Style::default().fg(Color::Red).bg(Color::Black));
+            let paragraph = Paragraph::new(format!(
+                "Terminal window is too short to display the help window\nresize the terminal or press \"{}\" to return to the test",
+                keybindings.describe(Action::Help)
+            ))
+            .style(Style::default().fg(Color::Red).bg(Color::Black));
 
             frame.render_widget(Clear, frame_rect);
             frame.render_widget(paragraph, frame_rect);
@@ -55,9 +92,10 @@ impl HelpWindow {
 
         // check if there is enough space horizontally to display the help message
         if frame_rect.width - 2 <= longest_help_msg_len as u16 {
-            let paragraph = Paragraph::new(
-                "Terminal window is too narrow\nto display the help window\nresize the terminal\nor press the \"?\" key\nto return to the test",
-            )
+            let paragraph = Paragraph::new(format!(
+                "Terminal window is too narrow\nto display the help window\nresize the terminal\nor press the \"{}\" key\nto return to the test",
+                keybindings.describe(Action::Help)
+            ))
             .style(Style::default().fg(Color::Red).bg(Color::Black));
 
             frame.render_widget(Clear, frame_rect);
@@ -98,7 +136,7 @@ impl HelpWindow {
         frame.render_widget(block, area);
 
         // Render text paragraphs
-        for (i, &text) in help_text.iter().enumerate() {
+        for (i, text) in help_text.into_iter().enumerate() {
             let paragraph = Paragraph::new(text);
             frame.render_widget(paragraph, chunks[i]);
         }
@@ -112,4 +150,46 @@ impl HelpWindow {
     }
 }
 
-// TODO: add tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybindings::parse_keys;
+    use crate::runner::FrameWrapper;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_text(config: &Config, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| HelpWindow::new().render(&mut FrameWrapper::new(frame), config))
+            .unwrap();
+
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+    }
+
+    #[test]
+    fn should_describe_configured_keys() {
+        let mut config = Config::default();
+        config.keybindings.help = parse_keys(&["f1".to_string()]).expect("Unable to parse keys");
+        config.keybindings.quit =
+            parse_keys(&["q".to_string(), "ctrl+q".to_string()]).expect("Unable to parse keys");
+
+        let text = render_to_text(&config, 160, 40);
+        assert!(text.contains("'<F1>'"));
+        assert!(text.contains("'q'/'<Ctrl+q>'"));
+        assert!(!text.contains("'?'"));
+
+        let text = render_to_text(&config, 100, 10);
+        assert!(text.contains("too short"));
+        assert!(text.contains("press \"<F1>\""));
+
+        let text = render_to_text(&config, 30, 40);
+        assert!(text.contains("too narrow"));
+        assert!(text.contains("\"<F1>\""));
+    }
+}
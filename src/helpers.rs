@@ -1,3 +1,5 @@
+use rand::{rngs::StdRng, SeedableRng};
+
 pub fn split_by_char_index(string: &str, char_index: usize) -> (&str, &str) {
     string
         .char_indices()
@@ -6,6 +8,86 @@ pub fn split_by_char_index(string: &str, char_index: usize) -> (&str, &str) {
         .unwrap_or((string, ""))
 }
 
+/// Collects characters from `start` index up to `end` index, both clamped to the length of `chars`
+pub fn get_chars_range(chars: &[char], start: usize, end: usize) -> String {
+    let end = end.min(chars.len());
+
+    chars[start.min(end)..end].iter().collect()
+}
+
+/// Returns the number of characters in the string up to the end of the `words_count`-th word,
+/// or `None` if the string doesn't contain that many finished words.
+///
+/// Word is considered finished when it is followed by a whitespace character.
+pub fn get_words_end_index(string: &str, words_count: usize) -> Option<usize> {
+    let mut finished_words_count = 0;
+    let mut previous_char: Option<char> = None;
+
+    for (index, char) in string.chars().enumerate() {
+        if char.is_whitespace() && previous_char.is_some_and(|c| !c.is_whitespace()) {
+            finished_words_count += 1;
+            if finished_words_count == words_count {
+                return Some(index);
+            }
+        }
+        previous_char = Some(char);
+    }
+
+    None
+}
+
+/// Returns the amount of characters of the string fitting in a line of the given `width`.
+///
+/// Line is broken after the last whitespace character fitting in it, so words aren't split
+/// between lines, unless a single word is longer than the whole line.
+pub fn get_line_len(string: &str, width: usize) -> usize {
+    let chars = string.chars().take(width + 1).collect::<Vec<_>>();
+    if chars.len() <= width {
+        return chars.len();
+    }
+
+    chars[..width]
+        .iter()
+        .rposition(|c| c.is_whitespace())
+        .map_or(width, |index| index + 1)
+}
+
+/// Counts the words in the string that are followed by a whitespace character.
+pub fn count_finished_words(string: &str) -> usize {
+    string
+        .chars()
+        .zip(string.chars().skip(1))
+        .filter(|(char, next_char)| !char.is_whitespace() && next_char.is_whitespace())
+        .count()
+}
+
+/// Creates random number generator used to generate expected input of the test
+///
+/// Generator created with the same seed always gives the same numbers,
+/// so the whole test can be generated again from its seed.
+pub fn get_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Calculates 64-bit FNV-1a hash of the string.
+///
+/// It's stable between program versions and platforms.
+pub fn get_numeric_hash(string: &str) -> u64 {
+    string.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Calculates 64-bit FNV-1a hash of the string and returns it as a hex string.
+///
+/// Used to identify the content of the text.
+pub fn get_hash(string: &str) -> String {
+    format!("{:016x}", get_numeric_hash(string))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -16,4 +98,30 @@ mod tests {
 
         assert_eq!((first_part, second_part), ("Բարեւ", " Ձեզ"));
     }
+
+    #[test]
+    fn should_get_words_end_index() {
+        assert_eq!(get_words_end_index("foo bar  baz ", 2), Some(7));
+        assert_eq!(get_words_end_index("foo bar baz", 3), None);
+    }
+
+    #[test]
+    fn should_get_line_len_at_word_boundary() {
+        assert_eq!(get_line_len("foo bar baz", 9), 8);
+        assert_eq!(get_line_len("foo bar baz", 8), 8);
+        assert_eq!(get_line_len("foo bar", 9), 7);
+        assert_eq!(get_line_len("foobarbaz qux", 6), 6);
+    }
+
+    #[test]
+    fn should_calculate_fnv_hash() {
+        assert_eq!(get_hash(""), "cbf29ce484222325");
+        assert_eq!(get_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn should_count_finished_words() {
+        assert_eq!(count_finished_words("foo bar  ba"), 2);
+        assert_eq!(count_finished_words(""), 0);
+    }
 }
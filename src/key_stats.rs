@@ -0,0 +1,203 @@
+//! Module with statistics of each key typed in the test.
+//!
+//! For every character of the expected input that the user tried to type
+//! the runner counts hits (the character was typed correctly) and misses (another character was
+//! typed in its place), together with the time that has passed since the previous keystroke.
+//! The same is counted for bigrams, the character together with the one preceding it.
+//!
+//! Statistics are saved in the results file as a single JSON column:
+//!
+//! ```json
+//! {
+//!     "keys": { "a": { "hits": 10, "misses": 1, "latency_sum": 1520, "latency_count": 9 } },
+//!     "bigrams": { "ha": { "hits": 4, "misses": 0, "latency_sum": 480, "latency_count": 4 } }
+//! }
+//! ```
+//!
+//! so they can be aggregated across all previous tests in the `history` view.
+
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Statistics of a single expected character
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// sum of milliseconds that have passed since the previous keystroke
+    pub latency_sum: u64,
+    /// amount of keystrokes with known latency, first keystroke of the test or after a pause
+    /// doesn't have one
+    pub latency_count: u64,
+}
+
+impl KeyStats {
+    /// Ratio of hits to all attempts of typing the key, in percents
+    pub fn get_accuracy(&self) -> f64 {
+        let attempts_count = self.hits + self.misses;
+        if attempts_count == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / attempts_count as f64 * 100.0
+    }
+
+    /// Average time in milliseconds it took to press the key
+    pub fn get_average_latency(&self) -> Option<f64> {
+        match self.latency_count {
+            0 => None,
+            latency_count => Some(self.latency_sum as f64 / latency_count as f64),
+        }
+    }
+
+    /// Adds statistics of the same key from other test
+    pub fn merge(&mut self, other: &KeyStats) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.latency_sum += other.latency_sum;
+        self.latency_count += other.latency_count;
+    }
+
+    fn record(&mut self, is_correct: bool, latency: Option<Duration>) {
+        match is_correct {
+            true => self.hits += 1,
+            false => self.misses += 1,
+        }
+        if let Some(latency) = latency {
+            self.latency_sum += latency.as_millis() as u64;
+            self.latency_count += 1;
+        }
+    }
+}
+
+/// Statistics of all keys typed in the test, by expected character, and of all bigrams
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeysStats {
+    #[serde(default)]
+    pub keys: BTreeMap<char, KeyStats>,
+    #[serde(default)]
+    pub bigrams: BTreeMap<String, KeyStats>,
+}
+
+impl KeysStats {
+    /// Counts an attempt of typing `expected_char`
+    ///
+    /// If the character is preceded by another one in the expected input,
+    /// the attempt is counted for their bigram as well.
+    pub fn record(
+        &mut self,
+        expected_char: char,
+        previous_char: Option<char>,
+        is_correct: bool,
+        latency: Option<Duration>,
+    ) {
+        self.keys
+            .entry(expected_char)
+            .or_default()
+            .record(is_correct, latency);
+
+        if let Some(previous_char) = previous_char {
+            self.bigrams
+                .entry(format!("{}{}", previous_char, expected_char))
+                .or_default()
+                .record(is_correct, latency);
+        }
+    }
+
+    /// Adds statistics of other test to these statistics
+    pub fn merge(&mut self, other: &KeysStats) {
+        for (key, other_key_stats) in &other.keys {
+            self.keys.entry(*key).or_default().merge(other_key_stats);
+        }
+        for (bigram, other_bigram_stats) in &other.bigrams {
+            self.bigrams
+                .entry(bigram.clone())
+                .or_default()
+                .merge(other_bigram_stats);
+        }
+    }
+}
+
+/// Statistics are saved as a JSON string, so they fit in a single column of the results file.
+pub mod json_column {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        keys_stats: &KeysStats,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let json = serde_json::to_string(keys_stats).map_err(S::Error::custom)?;
+
+        serializer.serialize_str(&json)
+    }
+
+    /// Missing or empty column, e.g. in results saved by older versions, gives empty statistics.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeysStats, D::Error> {
+        let json = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+        if json.is_empty() {
+            return Ok(KeysStats::default());
+        }
+
+        serde_json::from_str(&json).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_record_and_merge_keys_stats() {
+        let mut keys_stats = KeysStats::default();
+        keys_stats.record('a', None, true, None);
+        keys_stats.record('a', Some('b'), false, Some(Duration::from_millis(300)));
+
+        let mut other_keys_stats = KeysStats::default();
+        other_keys_stats.record('a', Some('b'), true, Some(Duration::from_millis(100)));
+        other_keys_stats.record('b', None, true, Some(Duration::from_millis(50)));
+
+        keys_stats.merge(&other_keys_stats);
+
+        let a_stats = keys_stats.keys[&'a'];
+        assert_eq!(a_stats.hits, 2);
+        assert_eq!(a_stats.misses, 1);
+        assert_eq!(a_stats.get_average_latency(), Some(200.0));
+        assert_eq!(keys_stats.keys[&'b'].get_accuracy(), 100.0);
+        assert_eq!(keys_stats.bigrams["ba"].get_accuracy(), 50.0);
+        assert_eq!(keys_stats.bigrams.len(), 1);
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Row {
+        #[serde(default, with = "json_column")]
+        keys_stats: KeysStats,
+    }
+
+    #[test]
+    fn should_serialize_keys_stats_as_json_string() {
+        let mut keys_stats = KeysStats::default();
+        keys_stats.record('a', None, true, Some(Duration::from_millis(120)));
+
+        let json = serde_json::to_string(&Row {
+            keys_stats: keys_stats.clone(),
+        })
+        .expect("Unable to serialize");
+
+        assert_eq!(
+            json,
+            r#"{"keys_stats":"{\"keys\":{\"a\":{\"hits\":1,\"misses\":0,\"latency_sum\":120,\"latency_count\":1}},\"bigrams\":{}}"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Row>(&json)
+                .expect("Unable to deserialize")
+                .keys_stats,
+            keys_stats
+        );
+        assert_eq!(
+            serde_json::from_str::<Row>(r#"{"keys_stats":""}"#)
+                .expect("Unable to deserialize")
+                .keys_stats,
+            KeysStats::default()
+        );
+    }
+}
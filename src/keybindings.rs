@@ -0,0 +1,507 @@
+//! Module with keys bound to actions of the program.
+//!
+//! Keys are set in `keybindings` section of the config file, where each action is bound
+//! to a list of keys, e.g. `"quit": ["q", "ctrl+c"]`.
+//! Key is either a single character, or one of `esc`, `enter`, `tab`, `backspace`, `delete`,
+//! `space`, `up`, `down`, `left`, `right`, `home`, `end`, `pageup`, `pagedown` and `f1` to `f12`,
+//! optionally preceded by `ctrl+`, `alt+` and `shift+` modifiers.
+//! `shift+` can't precede a character, as the shifted character is used instead, e.g. `A`.
+//!
+//! Actions are looked up in the context of the screen the key was pressed on, so the same key
+//! can be used by actions of different screens, but not by two actions of the same screen.
+//! While typing, keys that type a character, i.e. characters without `ctrl+` or `alt+`,
+//! are typed instead of triggering the actions, so actions used while typing need at least
+//! one other key.
+
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+
+/// Actions of the program triggered with keys
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// start or resume the test
+    Start,
+    /// quit the test, the results screen or the history
+    Quit,
+    /// toggle the help window
+    Help,
+    /// restart the test with a new text
+    Restart,
+    /// open or close the settings window
+    Settings,
+    /// pause the test
+    Pause,
+    /// remove the last word while typing
+    WordDelete,
+    /// finish the test in `zen` mode
+    Finish,
+    /// repeat the test with the same text from the results screen
+    Repeat,
+    /// start a new test from the results screen
+    Next,
+    /// toggle metric displayed on the keyboard diagram
+    ToggleHeatmap,
+    /// select the previous option in the settings window
+    SelectPrevious,
+    /// select the next option in the settings window
+    SelectNext,
+    /// set the previous value of the selected option in the settings window
+    PreviousValue,
+    /// set the next value of the selected option in the settings window
+    NextValue,
+    /// save options of the settings window to the config file
+    SaveSettings,
+    /// close the settings window
+    Close,
+}
+
+impl Action {
+    fn get_name(&self) -> &'static str {
+        match self {
+            Action::Start => "start",
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::Restart => "restart",
+            Action::Settings => "settings",
+            Action::Pause => "pause",
+            Action::WordDelete => "word_delete",
+            Action::Finish => "finish",
+            Action::Repeat => "repeat",
+            Action::Next => "next",
+            Action::ToggleHeatmap => "toggle_heatmap",
+            Action::SelectPrevious => "select_previous",
+            Action::SelectNext => "select_next",
+            Action::PreviousValue => "previous_value",
+            Action::NextValue => "next_value",
+            Action::SaveSettings => "save_settings",
+            Action::Close => "close",
+        }
+    }
+}
+
+/// Screens on which keys trigger different actions
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyContext {
+    /// test before it's started or when it's paused
+    Normal,
+    /// test while the user is typing
+    Editing,
+    /// results of the finished test
+    Results,
+    /// results of previous tests
+    History,
+    /// settings window opened on top of the test
+    Settings,
+}
+
+impl KeyContext {
+    fn get_actions(&self) -> &'static [Action] {
+        match self {
+            KeyContext::Normal => &[
+                Action::Start,
+                Action::Quit,
+                Action::Help,
+                Action::Restart,
+                Action::Settings,
+            ],
+            KeyContext::Editing => &[
+                Action::Pause,
+                Action::Restart,
+                Action::WordDelete,
+                Action::Finish,
+            ],
+            KeyContext::Results => &[
+                Action::Repeat,
+                Action::Next,
+                Action::ToggleHeatmap,
+                Action::Quit,
+            ],
+            KeyContext::History => &[Action::ToggleHeatmap, Action::Quit],
+            KeyContext::Settings => &[
+                Action::Settings,
+                Action::Close,
+                Action::SelectPrevious,
+                Action::SelectNext,
+                Action::PreviousValue,
+                Action::NextValue,
+                Action::SaveSettings,
+            ],
+        }
+    }
+}
+
+/// Single key with modifiers
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Parses key like `q`, `esc` or `ctrl+r`
+    pub fn parse(str: &str) -> Result<Self> {
+        let mut key = str;
+        let mut modifiers = KeyModifiers::NONE;
+        loop {
+            if let Some(rest) = key.strip_prefix("ctrl+").filter(|rest| !rest.is_empty()) {
+                modifiers |= KeyModifiers::CONTROL;
+                key = rest;
+            } else if let Some(rest) = key.strip_prefix("alt+").filter(|rest| !rest.is_empty()) {
+                modifiers |= KeyModifiers::ALT;
+                key = rest;
+            } else if let Some(rest) = key.strip_prefix("shift+").filter(|rest| !rest.is_empty()) {
+                modifiers |= KeyModifiers::SHIFT;
+                key = rest;
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key {
+                "esc" => KeyCode::Esc,
+                "enter" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                _ => match key.strip_prefix('f').and_then(|number| number.parse().ok()) {
+                    Some(number @ 1..=12) => KeyCode::F(number),
+                    _ => bail!("Unknown key \"{}\"", str),
+                },
+            },
+        };
+
+        // shift is already included in the character, e.g. `shift+a` is typed as `A`
+        if matches!(code, KeyCode::Char(_)) && modifiers.contains(KeyModifiers::SHIFT) {
+            bail!(
+                "Key \"{}\" can't be used with shift, use the shifted character instead",
+                str
+            );
+        }
+
+        Ok(Self { code, modifiers })
+    }
+
+    /// Checks if the key typed character instead of triggering an action while typing
+    fn is_typing(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+
+    /// Checks if the pressed key is this key
+    ///
+    /// `shift` modifier is ignored for characters, as it's already included in the character.
+    fn matches(&self, key: &KeyEvent) -> bool {
+        let ignored_modifiers = match self.code {
+            KeyCode::Char(_) => KeyModifiers::SHIFT,
+            _ => KeyModifiers::NONE,
+        };
+
+        self.code == key.code
+            && self.modifiers.difference(ignored_modifiers)
+                == key.modifiers.difference(ignored_modifiers)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    /// Displays character keys without modifiers as they are, and other keys in angle brackets,
+    /// e.g. `q`, `<Esc>`, `<Ctrl+r>`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::F(number) => format!("F{}", number),
+            code => format!("{:?}", code),
+        };
+        if self.modifiers.is_empty() && matches!(self.code, KeyCode::Char(c) if c != ' ') {
+            return write!(f, "{}", key);
+        }
+
+        let mut names = vec![];
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            names.push("Ctrl".to_string());
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            names.push("Alt".to_string());
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            names.push("Shift".to_string());
+        }
+        names.push(key);
+
+        write!(f, "<{}>", names.join("+"))
+    }
+}
+
+/// Keys bound to each action
+#[derive(Debug, Clone)]
+pub struct Keybindings {
+    pub start: Vec<KeyBinding>,
+    pub quit: Vec<KeyBinding>,
+    pub help: Vec<KeyBinding>,
+    pub restart: Vec<KeyBinding>,
+    pub settings: Vec<KeyBinding>,
+    pub pause: Vec<KeyBinding>,
+    pub word_delete: Vec<KeyBinding>,
+    pub finish: Vec<KeyBinding>,
+    pub repeat: Vec<KeyBinding>,
+    pub next: Vec<KeyBinding>,
+    pub toggle_heatmap: Vec<KeyBinding>,
+    pub select_previous: Vec<KeyBinding>,
+    pub select_next: Vec<KeyBinding>,
+    pub previous_value: Vec<KeyBinding>,
+    pub next_value: Vec<KeyBinding>,
+    pub save_settings: Vec<KeyBinding>,
+    pub close: Vec<KeyBinding>,
+}
+
+impl Keybindings {
+    pub fn default() -> Self {
+        let keys = |keys: &[&str]| {
+            keys.iter()
+                .map(|key| KeyBinding::parse(key).expect("Default key is valid"))
+                .collect()
+        };
+
+        Self {
+            start: keys(&["s"]),
+            quit: keys(&["q"]),
+            help: keys(&["?"]),
+            restart: keys(&["r", "ctrl+r"]),
+            settings: keys(&["o"]),
+            pause: keys(&["esc"]),
+            // Crossterm returns `ctrl+w` or `ctrl+h` when `ctrl+backspace` is pressed
+            // see: https://github.com/crossterm-rs/crossterm/issues/504
+            word_delete: keys(&["ctrl+w", "ctrl+h", "alt+backspace", "ctrl+backspace"]),
+            finish: keys(&["enter"]),
+            repeat: keys(&["r"]),
+            next: keys(&["n"]),
+            toggle_heatmap: keys(&["l"]),
+            select_previous: keys(&["up", "k"]),
+            select_next: keys(&["down", "j"]),
+            previous_value: keys(&["left", "h"]),
+            next_value: keys(&["right", "l"]),
+            save_settings: keys(&["w"]),
+            close: keys(&["esc"]),
+        }
+    }
+
+    /// Returns keys bound to the action
+    pub fn get_keys(&self, action: Action) -> &[KeyBinding] {
+        match action {
+            Action::Start => &self.start,
+            Action::Quit => &self.quit,
+            Action::Help => &self.help,
+            Action::Restart => &self.restart,
+            Action::Settings => &self.settings,
+            Action::Pause => &self.pause,
+            Action::WordDelete => &self.word_delete,
+            Action::Finish => &self.finish,
+            Action::Repeat => &self.repeat,
+            Action::Next => &self.next,
+            Action::ToggleHeatmap => &self.toggle_heatmap,
+            Action::SelectPrevious => &self.select_previous,
+            Action::SelectNext => &self.select_next,
+            Action::PreviousValue => &self.previous_value,
+            Action::NextValue => &self.next_value,
+            Action::SaveSettings => &self.save_settings,
+            Action::Close => &self.close,
+        }
+    }
+
+    /// Returns keys bound to the action that can be used in the context
+    fn get_context_keys(
+        &self,
+        action: Action,
+        context: KeyContext,
+    ) -> impl Iterator<Item = &KeyBinding> {
+        self.get_keys(action)
+            .iter()
+            .filter(move |key| context != KeyContext::Editing || !key.is_typing())
+    }
+
+    /// Returns the action triggered by the pressed key on the screen of the context
+    pub fn get_action(&self, key: &KeyEvent, context: KeyContext) -> Option<Action> {
+        context.get_actions().iter().copied().find(|action| {
+            self.get_context_keys(*action, context)
+                .any(|key_binding| key_binding.matches(key))
+        })
+    }
+
+    /// Describes the first key bound to the action, used in messages telling which key to press
+    ///
+    /// Returns `"none"` if there is no key bound to the action.
+    pub fn describe(&self, action: Action) -> String {
+        match self.get_keys(action).first() {
+            Some(key) => key.to_string(),
+            None => "none".to_string(),
+        }
+    }
+
+    /// Describes all keys bound to the action in quotes, separated with a slash
+    pub fn describe_all(&self, action: Action) -> String {
+        self.get_keys(action)
+            .iter()
+            .map(|key| format!("'{}'", key))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Checks that no key triggers two actions on the same screen,
+    /// and that actions used while typing aren't bound only to keys typing characters
+    pub fn validate(&self) -> Result<()> {
+        for action in KeyContext::Editing.get_actions() {
+            let keys = self.get_keys(*action);
+            if !keys.is_empty() && keys.iter().all(KeyBinding::is_typing) {
+                bail!(
+                    "\"{}\" is used while typing, so it needs a key with ctrl or alt, or a special key",
+                    action.get_name()
+                );
+            }
+        }
+
+        for context in [
+            KeyContext::Normal,
+            KeyContext::Editing,
+            KeyContext::Results,
+            KeyContext::History,
+            KeyContext::Settings,
+        ] {
+            let actions = context.get_actions();
+            for (index, action) in actions.iter().enumerate() {
+                for other_action in &actions[index + 1..] {
+                    let conflicting_key = self.get_context_keys(*action, context).find(|key| {
+                        self.get_context_keys(*other_action, context)
+                            .any(|other_key| other_key == *key)
+                    });
+                    if let Some(key) = conflicting_key {
+                        bail!(
+                            "Key {} is bound to both \"{}\" and \"{}\"",
+                            key,
+                            action.get_name(),
+                            other_action.get_name()
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses list of keys from the config file
+pub fn parse_keys(keys: &[String]) -> Result<Vec<KeyBinding>> {
+    keys.iter()
+        .map(|key| KeyBinding::parse(key).context("Unable to parse keybinding"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_and_display_keys() {
+        let keys = ["q", "?", "esc", "ctrl+r", "alt+backspace", "f5", "ctrl++"];
+
+        let keys = keys
+            .iter()
+            .map(|key| {
+                KeyBinding::parse(key)
+                    .expect("Unable to parse key")
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            keys,
+            vec![
+                "q",
+                "?",
+                "<Esc>",
+                "<Ctrl+r>",
+                "<Alt+Backspace>",
+                "<F5>",
+                "<Ctrl++>"
+            ]
+        );
+        assert!(KeyBinding::parse("ctrl+foo").is_err());
+        assert!(KeyBinding::parse("shift+a").is_err());
+        assert!(KeyBinding::parse("ctrl+shift+a").is_err());
+        assert!(KeyBinding::parse("shift+tab").is_ok());
+    }
+
+    #[test]
+    fn should_find_action_of_the_screen() {
+        let keybindings = Keybindings::default();
+        let r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE);
+        let ctrl_r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        let question_mark = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+
+        assert_eq!(
+            keybindings.get_action(&r, KeyContext::Normal),
+            Some(Action::Restart)
+        );
+        assert_eq!(keybindings.get_action(&r, KeyContext::Editing), None);
+        assert_eq!(
+            keybindings.get_action(&ctrl_r, KeyContext::Editing),
+            Some(Action::Restart)
+        );
+        assert_eq!(
+            keybindings.get_action(&r, KeyContext::Results),
+            Some(Action::Repeat)
+        );
+        assert_eq!(
+            keybindings.get_action(&question_mark, KeyContext::Normal),
+            Some(Action::Help)
+        );
+        assert_eq!(
+            keybindings.get_action(
+                &KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE),
+                KeyContext::Settings
+            ),
+            Some(Action::NextValue)
+        );
+        assert_eq!(
+            keybindings.get_action(
+                &KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+                KeyContext::Editing
+            ),
+            Some(Action::Finish)
+        );
+    }
+
+    #[test]
+    fn should_validate_conflicting_keys() {
+        let mut keybindings = Keybindings::default();
+        assert!(keybindings.validate().is_ok());
+
+        keybindings.help = parse_keys(&["q".to_string()]).expect("Unable to parse keys");
+        assert!(keybindings.validate().is_err());
+
+        // keys typing characters are not used while typing
+        keybindings.help = parse_keys(&["?".to_string()]).expect("Unable to parse keys");
+        keybindings.pause =
+            parse_keys(&["w".to_string(), "esc".to_string()]).expect("Unable to parse keys");
+        assert!(keybindings.validate().is_ok());
+
+        // action used while typing can't be triggered only with keys typing characters
+        keybindings.pause = parse_keys(&["w".to_string()]).expect("Unable to parse keys");
+        assert!(keybindings.validate().is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Module rendering statistics of each key on a keyboard diagram.
+//!
+//! Keys are drawn in rows of the `keyboard_layout` chosen in config
+//! and colored by the accuracy of typing them, or by the average time it took to press them.
+//! Uppercase letters and symbols typed with `<Shift>` are counted as their base keys,
+//! symbols are placed as on the US keyboard.
+
+use clap::ValueEnum;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::key_stats::{KeyStats, KeysStats};
+use crate::runner::FrameWrapperInterface;
+
+/// Enum used in config for choosing the keyboard layout of the heatmap.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+}
+
+impl KeyboardLayout {
+    fn get_rows(&self) -> [&'static str; 4] {
+        match self {
+            KeyboardLayout::Qwerty => ["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"],
+            KeyboardLayout::Dvorak => ["1234567890[]", "',.pyfgcrl/=", "aoeuidhtns-", ";qjkxbmwvz"],
+            KeyboardLayout::Colemak => {
+                ["1234567890-=", "qwfpgjluy;[]", "arstdhneio'", "zxcvbkm,./"]
+            }
+        }
+    }
+}
+
+/// Statistic used for coloring the keys
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HeatmapMetric {
+    Accuracy,
+    Latency,
+}
+
+impl HeatmapMetric {
+    /// Switches between accuracy and latency
+    pub fn toggle(self) -> Self {
+        match self {
+            HeatmapMetric::Accuracy => HeatmapMetric::Latency,
+            HeatmapMetric::Latency => HeatmapMetric::Accuracy,
+        }
+    }
+}
+
+/// Symbols typed with `<Shift>` and keys they are typed with
+const SHIFTED_SYMBOLS: [(char, char); 21] = [
+    ('!', '1'),
+    ('@', '2'),
+    ('#', '3'),
+    ('$', '4'),
+    ('%', '5'),
+    ('^', '6'),
+    ('&', '7'),
+    ('*', '8'),
+    ('(', '9'),
+    (')', '0'),
+    ('_', '-'),
+    ('+', '='),
+    ('{', '['),
+    ('}', ']'),
+    (':', ';'),
+    ('"', '\''),
+    ('<', ','),
+    ('>', '.'),
+    ('?', '/'),
+    ('~', '`'),
+    ('|', '\\'),
+];
+
+/// Amount of spaces each row of keys is shifted by
+const ROWS_OFFSETS: [usize; 4] = [0, 2, 3, 4];
+
+/// Returns the key on which the character is typed
+fn get_base_key(c: char) -> char {
+    SHIFTED_SYMBOLS
+        .iter()
+        .find(|(symbol, _)| *symbol == c)
+        .map(|(_, key)| *key)
+        .unwrap_or_else(|| c.to_ascii_lowercase())
+}
+
+/// Sums up statistics of characters typed on the same key
+fn get_base_keys_stats(keys_stats: &KeysStats) -> BTreeMap<char, KeyStats> {
+    let mut base_keys_stats: BTreeMap<char, KeyStats> = BTreeMap::new();
+    for (key, key_stats) in &keys_stats.keys {
+        base_keys_stats
+            .entry(get_base_key(*key))
+            .or_default()
+            .merge(key_stats);
+    }
+
+    base_keys_stats
+}
+
+/// Returns color of the key, or `None` if there is no data for it
+fn get_key_color(
+    key_stats: Option<&KeyStats>,
+    metric: HeatmapMetric,
+    average_latency: f64,
+) -> Option<Color> {
+    let key_stats = key_stats?;
+
+    match metric {
+        HeatmapMetric::Accuracy => {
+            if key_stats.hits + key_stats.misses == 0 {
+                return None;
+            }
+            match key_stats.get_accuracy() {
+                accuracy if accuracy >= 97.0 => Some(Color::Green),
+                accuracy if accuracy >= 90.0 => Some(Color::Yellow),
+                _ => Some(Color::Red),
+            }
+        }
+        HeatmapMetric::Latency => match key_stats.get_average_latency()? {
+            latency if latency <= average_latency => Some(Color::Green),
+            latency if latency <= average_latency * 1.3 => Some(Color::Yellow),
+            _ => Some(Color::Red),
+        },
+    }
+}
+
+/// Renders keyboard diagram with keys colored by given metric
+///
+/// Area should be at least 6 lines high, title and 5 rows of keys including the space bar.
+pub fn render_keyboard_heatmap(
+    frame: &mut impl FrameWrapperInterface,
+    area: Rect,
+    keys_stats: &KeysStats,
+    layout: KeyboardLayout,
+    metric: HeatmapMetric,
+) {
+    if keys_stats.keys.is_empty() {
+        return;
+    }
+
+    let base_keys_stats = get_base_keys_stats(keys_stats);
+    let latencies = base_keys_stats
+        .values()
+        .filter(|key_stats| key_stats.latency_count > 0)
+        .fold((0, 0), |(sum, count), key_stats| {
+            (sum + key_stats.latency_sum, count + key_stats.latency_count)
+        });
+    let average_latency = match latencies.1 {
+        0 => 0.0,
+        count => latencies.0 as f64 / count as f64,
+    };
+
+    let key_span = |label: String, key: char| {
+        let style = match get_key_color(base_keys_stats.get(&key), metric, average_latency) {
+            Some(color) => Style::default().fg(Color::Black).bg(color),
+            None => Style::default().fg(Color::DarkGray),
+        };
+        Span::styled(label, style)
+    };
+
+    let mut lines = layout
+        .get_rows()
+        .iter()
+        .zip(ROWS_OFFSETS)
+        .map(|(row, offset)| {
+            let mut spans = vec![Span::raw(" ".repeat(offset))];
+            spans.extend(row.chars().map(|key| key_span(format!(" {} ", key), key)));
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
+    lines.push(Line::from(vec![
+        Span::raw(" ".repeat(ROWS_OFFSETS[3] + 6)),
+        key_span(format!("{:^18}", "space"), ' '),
+    ]));
+
+    let title = match metric {
+        HeatmapMetric::Accuracy => {
+            "Accuracy per key (green >= 97%, yellow >= 90%, red below):".to_string()
+        }
+        HeatmapMetric::Latency => format!(
+            "Time to press per key (average {:.0} ms, yellow up to 30% slower, red slower):",
+            average_latency
+        ),
+    };
+
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().title(title)),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn should_count_shifted_characters_as_base_keys() {
+        let mut keys_stats = KeysStats::default();
+        keys_stats.record('A', None, false, None);
+        keys_stats.record('a', None, true, Some(Duration::from_millis(100)));
+        keys_stats.record('!', None, true, None);
+
+        let base_keys_stats = get_base_keys_stats(&keys_stats);
+
+        assert_eq!(base_keys_stats.len(), 2);
+        assert_eq!(base_keys_stats[&'a'].get_accuracy(), 50.0);
+        assert_eq!(base_keys_stats[&'1'].hits, 1);
+        assert_eq!(
+            get_key_color(base_keys_stats.get(&'a'), HeatmapMetric::Accuracy, 0.0),
+            Some(Color::Red)
+        );
+        assert_eq!(
+            get_key_color(base_keys_stats.get(&'1'), HeatmapMetric::Latency, 100.0),
+            None
+        );
+    }
+}
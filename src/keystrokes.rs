@@ -0,0 +1,207 @@
+//! Module with the keystroke event log of the test.
+//!
+//! Every key press in `Editing` mode, as well as pausing and resuming the test,
+//! is recorded by the runner together with the time at which it happened.
+//! Time is measured in milliseconds since the start of the test, without the time spent on pauses,
+//! so the log can be used to calculate speed over time, latency of each key or to replay the test.
+//!
+//! The log is saved next to the results file as a JSON file, one file per test,
+//! and path to it is saved in the `events_path` column of the results file:
+//!
+//! ```json
+//! [
+//!     { "time": 0, "kind": "char", "char": "f", "correct": true },
+//!     { "time": 180, "kind": "backspace" },
+//!     { "time": 420, "kind": "pause" }
+//! ]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{create_dir_all, File},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Kind of the recorded event.
+///
+/// `Char` is any typed character, including line breaks (`\n`) typed with `<Enter>`
+/// and tabs (`\t`) typed with `<Tab>`.
+/// `WordDelete` is removing the last word with `<Ctrl+Backspace>`, `<Alt+Backspace>` or `<Ctrl+w>`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeystrokeEventKind {
+    Char,
+    Backspace,
+    WordDelete,
+    Pause,
+    Resume,
+}
+
+/// Single entry of the keystroke event log.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeystrokeEvent {
+    /// milliseconds since the start of the test, without pauses
+    pub time: u64,
+    pub kind: KeystrokeEventKind,
+    /// typed character, only in `char` events
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub char: Option<char>,
+    /// tells if the character matched the expected input, only in `char` events
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub correct: Option<bool>,
+}
+
+impl KeystrokeEvent {
+    /// Creates an event of typing a character
+    pub fn char(elapsed: Duration, char: char, correct: bool) -> Self {
+        Self {
+            time: elapsed.as_millis() as u64,
+            kind: KeystrokeEventKind::Char,
+            char: Some(char),
+            correct: Some(correct),
+        }
+    }
+
+    /// Creates an event of any other kind
+    pub fn new(elapsed: Duration, kind: KeystrokeEventKind) -> Self {
+        Self {
+            time: elapsed.as_millis() as u64,
+            kind,
+            char: None,
+            correct: None,
+        }
+    }
+}
+
+/// WPM of a single second of the test.
+///
+/// `wpm` counts characters typed correctly since the start of the test up to the end of the second,
+/// `raw_wpm` counts all characters typed during that second only.
+/// `mistakes_count` is the number of characters typed incorrectly during that second.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WpmSample {
+    pub second: u64,
+    pub wpm: f64,
+    pub raw_wpm: f64,
+    pub mistakes_count: u64,
+}
+
+/// Splits the event log into seconds and calculates WPM of each of them
+///
+/// Timeline ends with the second of the last typed character.
+pub fn get_wpm_timeline(events: &[KeystrokeEvent]) -> Vec<WpmSample> {
+    let char_events = events
+        .iter()
+        .filter(|event| event.kind == KeystrokeEventKind::Char)
+        .collect::<Vec<_>>();
+    let seconds = match char_events.last() {
+        Some(last_event) => last_event.time / 1000 + 1,
+        None => return vec![],
+    };
+
+    let mut valid_characters_count = 0;
+    (1..=seconds)
+        .map(|second| {
+            let second_events = char_events
+                .iter()
+                .filter(|event| event.time / 1000 + 1 == second)
+                .collect::<Vec<_>>();
+            let mistakes_count = second_events
+                .iter()
+                .filter(|event| event.correct == Some(false))
+                .count() as u64;
+            valid_characters_count += second_events.len() as u64 - mistakes_count;
+
+            WpmSample {
+                second,
+                wpm: valid_characters_count as f64 / 5.0 * 60.0 / second as f64,
+                raw_wpm: second_events.len() as f64 / 5.0 * 60.0,
+                mistakes_count,
+            }
+        })
+        .collect()
+}
+
+/// Saves the event log as a JSON file in `dir_path` and returns the path to the file
+///
+/// Name of the file is built from the `id` of the test.
+pub fn save_events(dir_path: &Path, id: &str, events: &[KeystrokeEvent]) -> Result<PathBuf> {
+    if !dir_path.exists() {
+        create_dir_all(dir_path).context("Unable to create events directory")?;
+    }
+
+    let file_path = dir_path.join(format!("{}.json", id));
+    let file = File::create(&file_path).context("Unable to create events file")?;
+    serde_json::to_writer(file, events).context("Unable to write events to file")?;
+
+    Ok(file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_save_events_as_json() {
+        let dir = tempfile::tempdir().expect("Unable to create temp dir");
+        let events = vec![
+            KeystrokeEvent::char(Duration::ZERO, 'f', true),
+            KeystrokeEvent::new(Duration::from_millis(180), KeystrokeEventKind::Backspace),
+            KeystrokeEvent::new(Duration::from_millis(420), KeystrokeEventKind::Pause),
+        ];
+
+        let file_path =
+            save_events(&dir.path().join("events"), "test", &events).expect("Unable to save");
+
+        let content = std::fs::read_to_string(&file_path).expect("Unable to read events file");
+
+        assert_eq!(
+            content,
+            r#"[{"time":0,"kind":"char","char":"f","correct":true},{"time":180,"kind":"backspace"},{"time":420,"kind":"pause"}]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<KeystrokeEvent>>(&content).expect("Unable to parse events"),
+            events
+        );
+    }
+
+    #[test]
+    fn should_calculate_wpm_of_each_second() {
+        let events = vec![
+            KeystrokeEvent::char(Duration::from_millis(100), 'f', true),
+            KeystrokeEvent::char(Duration::from_millis(500), 'x', false),
+            KeystrokeEvent::new(Duration::from_millis(700), KeystrokeEventKind::Backspace),
+            KeystrokeEvent::char(Duration::from_millis(900), 'o', true),
+            KeystrokeEvent::char(Duration::from_millis(2500), 'o', true),
+            KeystrokeEvent::new(Duration::from_millis(3000), KeystrokeEventKind::Pause),
+        ];
+
+        let timeline = get_wpm_timeline(&events);
+
+        assert_eq!(
+            timeline,
+            vec![
+                WpmSample {
+                    second: 1,
+                    wpm: 24.0,
+                    raw_wpm: 36.0,
+                    mistakes_count: 1,
+                },
+                WpmSample {
+                    second: 2,
+                    wpm: 12.0,
+                    raw_wpm: 0.0,
+                    mistakes_count: 0,
+                },
+                WpmSample {
+                    second: 3,
+                    wpm: 12.0,
+                    raw_wpm: 12.0,
+                    mistakes_count: 0,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Module with progressive lessons used in `lesson` mode
+//!
+//! Lessons start with the home row letters and generate pseudo-words only from the letters
+//! unlocked so far.
+//! A new letter is unlocked once the user passes `LESSON_PASSES_TO_UNLOCK` tests in a row
+//! with at least `lesson_target_wpm` WPM and `lesson_target_accuracy` accuracy
+//! specified in config.
+//! The most recently unlocked letter is practised more often than the others.
+//!
+//! Lesson progress is saved in a JSON file next to the results file
+//! (`~/.local/share/donkeytype/donkeytype-lesson.json` on Linux and MacOS,
+//! and `C:\Users\{Username}\AppData\Local\donkeytype\donkeytype-lesson.json` on Windows).
+
+use anyhow::{Context, Result};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, File};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::expected_input::ExpectedInputInterface;
+use crate::helpers::{get_chars_range, get_rng};
+use crate::test_results::get_results_dir_path;
+
+/// Letters in the order they are unlocked, starting with the home row
+const LETTERS_ORDER: &str = "asdfjkleirutnohgymcvpwbxzq";
+
+/// Amount of letters unlocked at the beginning of the lessons
+const INITIAL_LETTERS_COUNT: usize = 7;
+
+/// Amount of tests in a row which have to meet the targets to unlock next letter
+const LESSON_PASSES_TO_UNLOCK: usize = 3;
+
+/// Range of lengths of generated pseudo-words
+const MIN_WORD_LEN: usize = 2;
+const MAX_WORD_LEN: usize = 6;
+
+/// Progress of the lessons saved between tests
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LessonState {
+    pub unlocked_letters_count: usize,
+    pub passed_tests_count: usize,
+}
+
+impl LessonState {
+    pub fn default() -> Self {
+        Self {
+            unlocked_letters_count: INITIAL_LETTERS_COUNT,
+            passed_tests_count: 0,
+        }
+    }
+
+    /// Reads lesson state from the file, or returns the initial state if the file doesn't exist
+    pub fn read(file_path: &Path) -> Result<Self> {
+        if !file_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(file_path).context("Unable to open lesson file")?;
+        let state = serde_json::from_reader(file).context("Unable to parse lesson file")?;
+
+        Ok(state)
+    }
+
+    pub fn save(&self, file_path: &Path) -> Result<()> {
+        if let Some(dir_path) = file_path.parent() {
+            create_dir_all(dir_path).context("Unable to create lesson file directory")?;
+        }
+
+        let file = File::create(file_path).context("Unable to create lesson file")?;
+        serde_json::to_writer(file, self).context("Unable to write lesson file")?;
+
+        Ok(())
+    }
+
+    pub fn get_unlocked_letters(&self) -> Vec<char> {
+        LETTERS_ORDER
+            .chars()
+            .take(self.unlocked_letters_count)
+            .collect()
+    }
+
+    /// Returns the letter that will be unlocked next, if there is any left
+    pub fn get_next_letter(&self) -> Option<char> {
+        LETTERS_ORDER.chars().nth(self.unlocked_letters_count)
+    }
+
+    /// Counts the result of the test and unlocks next letter if targets were met enough times
+    ///
+    /// Returns the letter that was unlocked.
+    pub fn update(&mut self, wpm: f64, accuracy: f64, config: &Config) -> Option<char> {
+        if wpm < config.lesson_target_wpm || accuracy < config.lesson_target_accuracy {
+            self.passed_tests_count = 0;
+            return None;
+        }
+
+        self.passed_tests_count += 1;
+        if self.passed_tests_count < LESSON_PASSES_TO_UNLOCK {
+            return None;
+        }
+
+        let next_letter = self.get_next_letter()?;
+        self.unlocked_letters_count += 1;
+        self.passed_tests_count = 0;
+
+        Some(next_letter)
+    }
+
+    /// Describes unlocked letters and what is needed to unlock the next one
+    pub fn get_progress_message(&self, unlocked_letter: Option<char>, config: &Config) -> String {
+        let unlocked_letters = self.get_unlocked_letters().into_iter().collect::<String>();
+        let next_target = match self.get_next_letter() {
+            Some(next_letter) => format!(
+                "'{}' unlocks after {} more test(s) with at least {:.0} WPM and {:.0}% accuracy",
+                next_letter,
+                LESSON_PASSES_TO_UNLOCK - self.passed_tests_count,
+                config.lesson_target_wpm,
+                config.lesson_target_accuracy
+            ),
+            None => "all letters are unlocked".to_string(),
+        };
+
+        match unlocked_letter {
+            Some(unlocked_letter) => format!(
+                "Lesson: new letter '{}' unlocked! Letters: {}, {}",
+                unlocked_letter, unlocked_letters, next_target
+            ),
+            None => format!("Lesson letters: {}, {}", unlocked_letters, next_target),
+        }
+    }
+}
+
+/// Returns path to the lesson state file in the data directory
+pub fn get_lesson_file_path() -> Result<PathBuf> {
+    let dir_path = get_results_dir_path().context("Unable to get results directory path")?;
+
+    Ok(dir_path.join("donkeytype-lesson.json"))
+}
+
+/// Struct used by runner to hold pseudo-words generated from unlocked letters
+#[derive(Debug)]
+pub struct ExpectedLesson {
+    pub letters: String,
+    chars: Vec<char>,
+}
+
+impl ExpectedLesson {
+    /// Create new struct instance with `words` pseudo-words specified in config
+    ///
+    /// Every other word contains the most recently unlocked letter.
+    pub fn new(config: &Config, state: &LessonState) -> Self {
+        let letters = state.get_unlocked_letters();
+        let focused_letter = *letters
+            .last()
+            .expect("Lessons always have unlocked letters");
+        let mut rng = get_rng(config.seed);
+
+        let words = (0..config.words)
+            .map(|index| {
+                let len = rng.gen_range(MIN_WORD_LEN..=MAX_WORD_LEN);
+                let mut word = (0..len)
+                    .map(|_| *letters.choose(&mut rng).expect("Letters are not empty"))
+                    .collect::<Vec<_>>();
+                if index % 2 == 0 && !word.contains(&focused_letter) {
+                    let position = rng.gen_range(0..len);
+                    word[position] = focused_letter;
+                }
+                word.into_iter().collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            letters: letters.into_iter().collect(),
+            chars: words.join(" ").chars().collect(),
+        }
+    }
+}
+
+impl ExpectedInputInterface for ExpectedLesson {
+    /// Returns characters of the generated words from the given range
+    fn get_range(&mut self, start: usize, end: usize) -> String {
+        get_chars_range(&self.chars, start, end)
+    }
+
+    fn get_len(&self) -> Option<usize> {
+        Some(self.chars.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_generate_words_from_unlocked_letters_only() {
+        let config = Config {
+            words: 20,
+            ..Config::default()
+        };
+        let state = LessonState::default();
+
+        let mut expected_lesson = ExpectedLesson::new(&config, &state);
+        let str = expected_lesson.get_range(0, usize::MAX);
+
+        assert_eq!(expected_lesson.letters, "asdfjkl");
+        assert_eq!(str.split_whitespace().count(), 20);
+        assert!(str.chars().all(|c| c == ' ' || "asdfjkl".contains(c)));
+        assert!(str
+            .split_whitespace()
+            .step_by(2)
+            .all(|word| word.contains('l')));
+    }
+
+    #[test]
+    fn should_unlock_next_letter_after_passing_tests_in_a_row() {
+        let config = Config::default();
+        let mut state = LessonState::default();
+
+        assert_eq!(state.update(100.0, 100.0, &config), None);
+        assert_eq!(state.update(100.0, 100.0, &config), None);
+        assert_eq!(state.update(10.0, 100.0, &config), None);
+        assert_eq!(state.passed_tests_count, 0);
+
+        for _ in 0..LESSON_PASSES_TO_UNLOCK - 1 {
+            assert_eq!(state.update(100.0, 100.0, &config), None);
+        }
+        assert_eq!(state.update(100.0, 100.0, &config), Some('e'));
+        assert_eq!(
+            state.get_unlocked_letters().len(),
+            INITIAL_LETTERS_COUNT + 1
+        );
+        assert_eq!(state.get_next_letter(), Some('i'));
+    }
+
+    #[test]
+    fn should_save_and_read_lesson_state() {
+        let dir = tempfile::tempdir().expect("Unable to create temp dir");
+        let file_path = dir.path().join("lesson.json");
+
+        assert_eq!(
+            LessonState::read(&file_path).expect("Unable to read lesson state"),
+            LessonState::default()
+        );
+
+        let state = LessonState {
+            unlocked_letters_count: 10,
+            passed_tests_count: 2,
+        };
+        state.save(&file_path).expect("Unable to save lesson state");
+
+        assert_eq!(
+            LessonState::read(&file_path).expect("Unable to read lesson state"),
+            state
+        );
+    }
+}
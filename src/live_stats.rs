@@ -0,0 +1,73 @@
+//! Module with statistics displayed in the stats bar while the test is running.
+//!
+//! Correctness of each character of user input is remembered when it's typed,
+//! and forgotten when it's removed, so the amount of valid characters is known at any time
+//! without comparing the whole input with the expected input on every frame.
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Enum used in config for choosing which statistics are displayed in the stats bar.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveStat {
+    /// words per minute counted in valid characters of the input
+    Wpm,
+    /// words per minute counted in every typed character, including mistakes
+    RawWpm,
+    /// percentage of valid key presses
+    Accuracy,
+    /// amount of invalid key presses
+    Errors,
+}
+
+/// Correctness of characters of user input, updated with each typed and removed character
+#[derive(Debug, Default)]
+pub struct LiveStats {
+    input_correctness: Vec<bool>,
+    valid_characters_count: usize,
+}
+
+impl LiveStats {
+    /// Remembers correctness of the character added to user input
+    pub fn push(&mut self, is_correct: bool) {
+        self.input_correctness.push(is_correct);
+        if is_correct {
+            self.valid_characters_count += 1;
+        }
+    }
+
+    /// Forgets correctness of characters removed from user input, so it has `len` characters
+    pub fn truncate(&mut self, len: usize) {
+        while self.input_correctness.len() > len {
+            if self.input_correctness.pop() == Some(true) {
+                self.valid_characters_count -= 1;
+            }
+        }
+    }
+
+    /// Amount of valid characters in user input
+    pub fn get_valid_characters_count(&self) -> usize {
+        self.valid_characters_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_count_valid_characters_of_input() {
+        let mut live_stats = LiveStats::default();
+
+        live_stats.push(true);
+        live_stats.push(false);
+        live_stats.push(true);
+        assert_eq!(live_stats.get_valid_characters_count(), 2);
+
+        live_stats.truncate(1);
+        assert_eq!(live_stats.get_valid_characters_count(), 1);
+        live_stats.truncate(5);
+        assert_eq!(live_stats.get_valid_characters_count(), 1);
+    }
+}
@@ -18,21 +18,53 @@
 //!
 //! Default options of configuration are:
 //!
-//! | name              | default value                | type in JSON | description                                                                                                                                                                                                           |
-//! | ----------------- | ---------------------------- | ------------ | --------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
-//! | `duration`        | `30`                         | number       | duration of the test in seconds                                                                                                                                                                                       |
-//! | `numbers`         | `false`                      | boolean      | flag indicating if numbers should be inserted in expected input                                                                                                                                                       |
-//! | `numbers_ratio`   | `0.05` (if numbers=true)     | number       | ratio for putting numbers in the test                                                                                                                                                                                 |
-//! | `symbols`         | `false`                      | boolean      | flag indicating if symbols should be inserted in expected input                                                                                                                                                       |
-//! | `symbols_ratio`   | `0.10` (if symbols=true)     | number       | ratio for putting symbols in the test                                                                                                                                                                                 |
-//! | `uppercase`       | `false`                      | boolean      | flag indicating if uppercase letters should be inserted in expected input                                                                                                                                             |
-//! | `uppercase_ratio` | `0.15` (if uppercase=true)   | boolean      | ratio for putting uppercase letters in test                                                                                                                                                                           |
-//! | `dictionary_path` |  `None` (builtin dictionary) | string       | path to file with dictionary words to sample from while creating test's expected input                                                                                                                                |
-//! | `save_results`    | `true`                       | boolean      | flag indicating if results should be saved to a file  ( `~/.local/share/donkeytype/donkeytype-results.csv`  on Linux and MacOS, and `C:\Users\{Username}\AppData\Local\donkeytype\donkeytype-results.csv` on Windows) |
+//! | name                     | default value                      | type in JSON    | description                                                                                                                                                                                                                                                                                                                                                                       |
+//! | ------------------------ | ---------------------------------- | --------------- | --------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+//! | `duration`               | `30`                               | number          | duration of the test in seconds                                                                                                                                                                                                                                                                                                                                                   |
+//! | `mode`                   | `"time"`                           | string          | mode of the test, `"time"` ends the test after `duration` seconds, `"words"` once `words` words were typed, `"quote"` once the whole quote was typed, `"text"` once the whole `text_path` file was typed or `duration` passed, `"code"` once the whole code snippet was typed, `"zen"` once `<Enter>` was pressed, `"lesson"` once `words` words from unlocked letters were typed |
+//! | `words`                  | `50`                               | number          | amount of words to type in `words` and `lesson` modes                                                                                                                                                                                                                                                                                                                             |
+//! | `quote_length`           | `"all"`                            | string          | length of the quotes to pick from in `quote` mode, one of `"all"`, `"short"`, `"medium"`, `"long"`                                                                                                                                                                                                                                                                                |
+//! | `quotes_path`            | `None` (builtin quotes)            | string          | path to JSON file with quotes (`[{ "text": "...", "source": "..." }]`) to pick from in `quote` mode                                                                                                                                                                                                                                                                               |
+//! | `text_path`              | `None`                             | string          | path to text file to type verbatim in `text` mode, `"-"` reads the text from standard input                                                                                                                                                                                                                                                                                       |
+//! | `keep_newlines`          | `false`                            | boolean         | flag indicating if line breaks of the text should be kept and typed with `<Enter>` in `text` mode                                                                                                                                                                                                                                                                                 |
+//! | `code_language`          | `"rust"`                           | string          | language of builtin code snippets in `code` mode, one of `"rust"`, `"python"`, `"javascript"`                                                                                                                                                                                                                                                                                     |
+//! | `code_path`              | `None` (builtin snippets)          | string          | path to file with code to type in `code` mode                                                                                                                                                                                                                                                                                                                                     |
+//! | `skip_indentation`       | `true`                             | boolean         | flag indicating if indentation should be skipped automatically after typing a line break in `code` mode                                                                                                                                                                                                                                                                           |
+//! | `adaptive`               | `false`                            | boolean         | flag indicating if words with letters and bigrams you make most mistakes on or type slowest should appear more often in `time` and `words` modes                                                                                                                                                                                                                                  |
+//! | `lesson_target_wpm`      | `35`                               | number          | WPM needed to unlock next letter in `lesson` mode                                                                                                                                                                                                                                                                                                                                 |
+//! | `lesson_target_accuracy` | `95`                               | number          | accuracy in percents, before corrections, needed to unlock next letter in `lesson` mode                                                                                                                                                                                                                                                                                           |
+//! | `numbers`                | `false`                            | boolean         | flag indicating if numbers should be inserted in expected input                                                                                                                                                                                                                                                                                                                   |
+//! | `numbers_ratio`          | `0.05` (if numbers=true)           | number          | ratio for putting numbers in the test                                                                                                                                                                                                                                                                                                                                             |
+//! | `number_formats`         | `[]`                               | list of strings | formats of numbers, `#` is replaced with a random digit, e.g. `19##` or `#.##`, by default numbers are as long as replaced words                                                                                                                                                                                                                                                  |
+//! | `symbols`                | `false`                            | boolean         | flag indicating if symbols should be inserted in expected input                                                                                                                                                                                                                                                                                                                   |
+//! | `symbols_ratio`          | `0.10` (if symbols=true)           | number          | ratio for putting symbols in the test                                                                                                                                                                                                                                                                                                                                             |
+//! | `ending_symbols`         | `[".", ",", "!", "?"]`             | list of strings | symbols appended to words, e.g. `->` or `::`                                                                                                                                                                                                                                                                                                                                      |
+//! | `surrounding_symbols`    | `["[]", "{}", "()", "\"\"", "''"]` | list of strings | pairs of symbols surrounding words, each pair is split in the middle, e.g. `<<>>`                                                                                                                                                                                                                                                                                                 |
+//! | `punctuation`            | `false`                            | boolean         | flag indicating if words should be built into sentences, starting with a capital letter and ending with `.`, `?` or `!`, with commas, apostrophes and quotes                                                                                                                                                                                                                      |
+//! | `sentence_end_ratio`     | `0.12` (if punctuation=true)       | number          | ratio of words ending a sentence with `.`, `?` or `!` in punctuation mode                                                                                                                                                                                                                                                                                                         |
+//! | `comma_ratio`            | `0.08` (if punctuation=true)       | number          | ratio of words followed by a comma in punctuation mode                                                                                                                                                                                                                                                                                                                            |
+//! | `apostrophe_ratio`       | `0.03` (if punctuation=true)       | number          | ratio of words getting an apostrophe (`"dog's"`) in punctuation mode                                                                                                                                                                                                                                                                                                              |
+//! | `quote_ratio`            | `0.03` (if punctuation=true)       | number          | ratio of words opening a quote around a few words in punctuation mode                                                                                                                                                                                                                                                                                                             |
+//! | `uppercase`              | `false`                            | boolean         | flag indicating if uppercase letters should be inserted in expected input                                                                                                                                                                                                                                                                                                         |
+//! | `uppercase_ratio`        | `0.15` (if uppercase=true)         | boolean         | ratio for putting uppercase letters in test                                                                                                                                                                                                                                                                                                                                       |
+//! | `max_width`              | `80`                               | number          | maximal width of the typed text in characters, the text is centred in wider terminals, `0` means no limit                                                                                                                                                                                                                                                                         |
+//! | `live_stats`             | `[]`                               | list of strings | statistics displayed in the stats bar while typing, any of `"wpm"`, `"raw_wpm"`, `"accuracy"` and `"errors"`                                                                                                                                                                                                                                                                      |
+//! | `focus`                  | `false`                            | boolean         | flag indicating if everything but the text, including time left and stats bar, should be hidden while typing                                                                                                                                                                                                                                                                      |
+//! | `type_to_start`          | `false`                            | boolean         | flag indicating if the test should start with the first typed character, counted as input, instead of the start key, the timer starts with that character and the start key only resumes a paused test                                                                                                                                                                            |
+//! | `dictionary_path`        | `None` (builtin dictionary)        | string          | path to file with dictionary words, one per line, optionally followed by a tab and the frequency of the word (`word<TAB>count`), frequent words are sampled more often                                                                                                                                                                                                            |
+//! | `language`               | `"english"`                        | string          | language of the builtin dictionary used when `dictionary_path` is not set, one of `"english_200"`, `"english"`, `"german"`, `"spanish"`, `"french"`, `"polish"`, `"russian"`, run with `--list-languages` to see them all                                                                                                                                                         |
+//! | `min_word_length`        | `None`                             | number          | minimal length in characters of words sampled from the dictionary                                                                                                                                                                                                                                                                                                                 |
+//! | `max_word_length`        | `None`                             | number          | maximal length in characters of words sampled from the dictionary                                                                                                                                                                                                                                                                                                                 |
+//! | `exclude_words`          | `[]`                               | array           | words that should never appear in the test, e.g. `["the", "a"]`, in arguments separated with commas                                                                                                                                                                                                                                                                               |
+//! | `generator`              | `"words"`                          | string          | how the text is generated in `time` and `words` modes, `"words"` samples random words from the dictionary, `"markov"` generates sentences with a Markov chain trained on `corpus_path`                                                                                                                                                                                            |
+//! | `corpus_path`            | `None` (builtin corpus)            | string          | path to text file the Markov chain is trained on when `generator` is `"markov"`                                                                                                                                                                                                                                                                                                   |
+//! | `save_results`           | `true`                             | boolean         | flag indicating if results should be saved to a file ( `~/.local/share/donkeytype/donkeytype-results.csv` on Linux and MacOS, and `C:\Users\{Username}\AppData\Local\donkeytype\donkeytype-results.csv` on Windows)                                                                                                                                                               |
+//! | `keyboard_layout`        | `"qwerty"`                         | string          | keyboard layout of the per key statistics diagram, one of `"qwerty"`, `"dvorak"`, `"colemak"`                                                                                                                                                                                                                                                                                     |
+//! | `seed`                   | `None` (random)                    | number          | seed of the random generator used to generate the test, tests with the same seed and options have the same text, seed of each test is saved in its results                                                                                                                                                                                                                        |
 //!
-//! NOTE: If provided `numbers_ratio` is not between `0` to `1.0`, default `numbers_ratio = 0.15` will be used. Same happens with `uppercase_ratio` and `symbols_ratio`.
+//! NOTE: If provided `numbers_ratio` is not between `0` to `1.0`, default `numbers_ratio = 0.15` will be used. Same happens with `uppercase_ratio`, `symbols_ratio` and the ratios of punctuation mode.
 //!
-//! Configuration will grow when more features are added (_different modes_, _different languages_).
+//! Configuration will grow when more features are added (_different modes_).
 //!
 //! You can provide this config by putting it in a config file in `~/.config/donkeytype/donkeytype-config.json`:
 //!
@@ -68,17 +100,37 @@
 //! ```
 
 mod args;
+mod code_input;
+mod code_snippets;
 mod color_scheme;
 mod config;
+mod corpus;
+mod daily;
 mod dictionary;
 mod expected_input;
 mod help_window;
 mod helpers;
+mod key_stats;
+mod keybindings;
+mod keyboard;
+mod keystrokes;
+mod lesson;
+mod live_stats;
+mod markov_input;
+mod quote_input;
+mod quotes;
 mod runner;
+mod settings_window;
+mod test_clock;
+mod test_mode;
 mod test_results;
+mod text_input;
+mod weak_keys;
+mod zen_input;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use chrono::Local;
+use clap::{Parser, ValueEnum};
 use crossterm::execute;
 use crossterm::terminal::supports_keyboard_enhancement;
 use crossterm::{
@@ -88,51 +140,77 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 use std::path::PathBuf;
-use test_results::{read_previous_results, render_results};
+use test_results::{read_previous_results, render_results, ResultsAction};
 
-use args::Args;
+use args::{Args, SubCommand};
+use code_input::ExpectedCode;
 use config::Config;
-use expected_input::ExpectedInput;
-use runner::Runner;
+use daily::{apply_daily_options, get_daily_summary};
+use dictionary::Language;
+use expected_input::{ExpectedInput, ExpectedInputInterface};
+use lesson::{get_lesson_file_path, ExpectedLesson, LessonState};
+use markov_input::{ExpectedMarkov, Generator};
+use quote_input::ExpectedQuote;
+use runner::{Runner, TestOutcome};
+use settings_window::SettingsWindow;
+use test_mode::TestMode;
+use text_input::{read_stdin_text, ExpectedText};
+use weak_keys::WeakKeys;
+use zen_input::ExpectedNothing;
 
 /// main entry to the program
 /// - parses arguments,
-/// - reads config
+/// - reads config, and the text from standard input if it's typed in `text` mode
 /// - creates expected input
 /// - prepares terminal window
 /// - starts the test
-/// - restores terminal configuration
-/// - if test was completed, prints the results and saves them.
+/// - if test was completed, prints the results and saves them
+/// - starts next tests until the user quits
+/// - restores terminal configuration.
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    if args.list_languages {
+        print_languages();
+        return Ok(());
+    }
+
+    let command = args.history.clone();
+    let mut config = get_config(args)?;
+    // text piped to the program is read before the terminal is switched to raw mode
+    read_stdin_text(&mut config, io::stdin()).context("Unable to read text")?;
+
     let mut terminal = configure_terminal().context("Unable to configure terminal")?;
 
-    let res = match &args.history {
-        Some(_) => handle_history_command(&mut terminal),
-        None => handle_main_command(&mut terminal, args),
+    let res = match command {
+        Some(SubCommand::History(_)) => handle_history_command(&mut terminal, config),
+        Some(SubCommand::Daily(_)) => handle_daily_command(&mut terminal, config),
+        None => handle_main_command(&mut terminal, config),
     };
 
     match res {
         Err(err) => {
             restore_terminal(&mut terminal).context("Unable to restore terminal")?;
-            return Err(err);
+            Err(err)
         }
         Ok(_) => Ok(()),
     }
 }
 
-fn handle_history_command(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
-    let records = read_previous_results().context("Unable to read history results")?;
-    render_results(terminal, &records).context("Unable to render history results")?;
-    restore_terminal(terminal).context("Unable to restore terminal")?;
-    Ok(())
+/// prints languages of the builtin dictionary with the amount of words in each of them
+fn print_languages() {
+    println!("Available languages:");
+    for language in Language::value_variants() {
+        println!(
+            "  {:<12} {} words",
+            language.get_name(),
+            language.get_words().len()
+        );
+    }
 }
 
-fn handle_main_command(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    args: Args,
-) -> Result<()> {
+/// reads config from the config file and arguments
+fn get_config(args: Args) -> Result<Config> {
     let config_file_path = args
         .config_path
         .clone()
@@ -148,29 +226,258 @@ fn handle_main_command(
             base_dir.join("donkeytype").join("donkeytype-config.json")
         });
 
-    let config = Config::new(args, config_file_path).context("Unable to create config")?;
-    let expected_input = ExpectedInput::new(&config).context("Unable to create expected input")?;
+    Config::new(args, config_file_path).context("Unable to create config")
+}
+
+fn handle_history_command(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    config: Config,
+) -> Result<()> {
+    let records = read_previous_results().context("Unable to read history results")?;
+    render_results(
+        terminal,
+        &records,
+        config.keyboard_layout,
+        &config.keybindings,
+    )
+    .context("Unable to render history results")?;
+    restore_terminal(terminal).context("Unable to restore terminal")?;
+    Ok(())
+}
+
+fn handle_main_command(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    mut config: Config,
+) -> Result<()> {
+    // every test has a seed, so that it can be generated again from the results
+    if config.seed.is_none() {
+        config.seed = Some(rand::random());
+    }
+
+    run_session(terminal, config)
+}
+
+/// runs the daily challenge, options deciding about the content of the test are overwritten
+fn handle_daily_command(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    mut config: Config,
+) -> Result<()> {
+    apply_daily_options(&mut config, Local::now().date_naive());
+
+    run_session(terminal, config)
+}
+
+/// runs tests one after another in the same terminal until the user quits
+///
+/// After each completed test its results are shown and saved, and the user chooses
+/// whether to repeat the test with the same text, start a new test or quit.
+/// New tests get a new seed, except for the daily challenge which has the same text all day.
+fn run_session(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    mut config: Config,
+) -> Result<()> {
+    let mut settings_window = None;
+    let mut repeated_input = None;
 
-    let mut app = Runner::new(config, expected_input);
-    let test_results = app.run(terminal).context("Error while running the test")?;
+    loop {
+        let test_input = match repeated_input.take() {
+            Some(test_input) => test_input,
+            None => TestInput::create(&config)?,
+        };
+        let (outcome, test_input) =
+            run_test(terminal, config.clone(), settings_window.take(), test_input)?;
+
+        let action = match outcome {
+            TestOutcome::Restart {
+                config: restart_config,
+                settings_window: restart_settings_window,
+            } => {
+                config = *restart_config;
+                settings_window = restart_settings_window;
+                ResultsAction::Next
+            }
+            TestOutcome::Finished(mut test_results) if test_results.completed => {
+                if test_results.daily == Some(true) {
+                    let previous_results =
+                        read_previous_results().context("Unable to read previous results")?;
+                    test_results.daily_summary =
+                        Some(get_daily_summary(&previous_results, &test_results));
+                }
+                let action = test_results
+                    .render(terminal, &config.keybindings)
+                    .context("Unable to render test results")?;
+                if test_results.save {
+                    test_results
+                        .save_to_file()
+                        .context("Unable to save results to file")?;
+                }
+                action
+            }
+            TestOutcome::Finished(_) => {
+                restore_terminal(terminal).context("Unable to restore terminal")?;
+                println!("Test not finished.");
+                return Ok(());
+            }
+        };
 
-    if test_results.completed {
-        test_results
-            .render(terminal)
-            .context("Unable to render test results")?;
-        if test_results.save {
-            test_results
-                .save_to_file()
-                .context("Unable to save results to file")?;
+        match action {
+            ResultsAction::Repeat => repeated_input = Some(test_input),
+            ResultsAction::Next if config.daily => {}
+            ResultsAction::Next => config.seed = Some(rand::random()),
+            ResultsAction::Quit => break,
         }
-        restore_terminal(terminal).context("Unable to restore terminal")?;
-    } else {
-        restore_terminal(terminal).context("Unable to restore terminal")?;
-        println!("Test not finished.");
     }
+
+    restore_terminal(terminal).context("Unable to restore terminal")?;
     Ok(())
 }
 
+/// expected input of a test together with details about it which are saved in the results
+///
+/// It's kept after the test is finished, so that repeating the test gives exactly the same text,
+/// even in modes where creating the expected input again would change it: `adaptive` mode samples
+/// words by results which include the finished test, and `lesson` mode may unlock a new letter.
+struct TestInput {
+    expected_input: Box<dyn ExpectedInputInterface>,
+    quote_id: Option<usize>,
+    quote_source: Option<String>,
+    text_path: Option<String>,
+    text_hash: Option<String>,
+    lesson_letters: Option<String>,
+}
+
+impl TestInput {
+    fn new(expected_input: impl ExpectedInputInterface + 'static) -> Self {
+        Self {
+            expected_input: Box::new(expected_input),
+            quote_id: None,
+            quote_source: None,
+            text_path: None,
+            text_hash: None,
+            lesson_letters: None,
+        }
+    }
+
+    /// creates expected input for the mode of the test
+    fn create(config: &Config) -> Result<Self> {
+        let test_input = match config.mode {
+            TestMode::Quote => {
+                let expected_quote =
+                    ExpectedQuote::new(config).context("Unable to create expected quote")?;
+                Self {
+                    quote_id: Some(expected_quote.id),
+                    quote_source: Some(expected_quote.source.clone()),
+                    ..Self::new(expected_quote)
+                }
+            }
+            TestMode::Text => {
+                let expected_text =
+                    ExpectedText::new(config).context("Unable to create expected text")?;
+                Self {
+                    text_path: Some(expected_text.path.clone()),
+                    text_hash: Some(expected_text.hash.clone()),
+                    ..Self::new(expected_text)
+                }
+            }
+            TestMode::Code => {
+                Self::new(ExpectedCode::new(config).context("Unable to create expected code")?)
+            }
+            TestMode::Lesson => {
+                let lesson_file_path =
+                    get_lesson_file_path().context("Unable to get lesson file path")?;
+                let lesson_state =
+                    LessonState::read(&lesson_file_path).context("Unable to read lesson state")?;
+                let expected_lesson = ExpectedLesson::new(config, &lesson_state);
+                Self {
+                    lesson_letters: Some(expected_lesson.letters.clone()),
+                    ..Self::new(expected_lesson)
+                }
+            }
+            TestMode::Zen => Self::new(ExpectedNothing),
+            TestMode::Time | TestMode::Words if config.generator == Generator::Markov => {
+                Self::new(ExpectedMarkov::new(config).context("Unable to create expected markov")?)
+            }
+            TestMode::Time | TestMode::Words => {
+                let expected_input = match config.adaptive {
+                    true => {
+                        let previous_results =
+                            read_previous_results().context("Unable to read previous results")?;
+                        let weak_keys = WeakKeys::from_results(&previous_results);
+                        ExpectedInput::new_with_weak_keys(config, &weak_keys)
+                    }
+                    false => ExpectedInput::new(config),
+                }
+                .context("Unable to create expected input")?;
+                Self::new(expected_input)
+            }
+        };
+
+        Ok(test_input)
+    }
+}
+
+/// runs the test with the given input, which is returned together with the outcome of the test
+///
+/// `settings_window` is opened on top of the test if the previous test was restarted
+/// by changing options in it.
+fn run_test(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    config: Config,
+    settings_window: Option<SettingsWindow>,
+    test_input: TestInput,
+) -> Result<(TestOutcome, TestInput)> {
+    let lesson_config = config.clone();
+    let TestInput {
+        expected_input,
+        quote_id,
+        quote_source,
+        text_path,
+        text_hash,
+        lesson_letters,
+    } = test_input;
+
+    let mut app = Runner::new(config, expected_input).with_settings_window(settings_window);
+    let mut outcome = app.run(terminal).context("Error while running the test")?;
+
+    if let TestOutcome::Finished(test_results) = &mut outcome {
+        test_results.quote_id = quote_id;
+        test_results.quote_source = quote_source.clone();
+        test_results.text_path = text_path.clone();
+        test_results.text_hash = text_hash.clone();
+        test_results.lesson_letters = lesson_letters.clone();
+
+        if test_results.completed && lesson_config.mode == TestMode::Lesson {
+            let lesson_file_path =
+                get_lesson_file_path().context("Unable to get lesson file path")?;
+            let mut lesson_state =
+                LessonState::read(&lesson_file_path).context("Unable to read lesson state")?;
+            let unlocked_letter = lesson_state.update(
+                test_results.wpm.unwrap_or_default(),
+                test_results.raw_accuracy.unwrap_or_default(),
+                &lesson_config,
+            );
+            test_results.lesson_progress =
+                Some(lesson_state.get_progress_message(unlocked_letter, &lesson_config));
+            if test_results.save {
+                lesson_state
+                    .save(&lesson_file_path)
+                    .context("Unable to save lesson state")?;
+            }
+        }
+    }
+
+    let test_input = TestInput {
+        expected_input: app.into_expected_input(),
+        quote_id,
+        quote_source,
+        text_path,
+        text_hash,
+        lesson_letters,
+    };
+
+    Ok((outcome, test_input))
+}
+
 /// prepares terminal window for rendering using tui
 fn configure_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, anyhow::Error> {
     enable_raw_mode().context("Unable to enable raw mode")?;
@@ -212,7 +519,11 @@ fn restore_terminal(
 
 #[cfg(test)]
 mod tests {
-    use std::{io::Write, path::PathBuf, time::Instant};
+    use std::{
+        io::Write,
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
 
     use anyhow::{Context, Result};
     use predicates::Predicate;
@@ -239,8 +550,7 @@ mod tests {
             for x in 0..buffer.area.height {
                 let cell = buffer.cell(Position { x, y });
                 text.push_str(
-                    &cell
-                        .context("Unable to get cell from the terminal buffer")?
+                    cell.context("Unable to get cell from the terminal buffer")?
                         .symbol(),
                 );
             }
@@ -294,18 +604,50 @@ mod tests {
             config_path: None,
             save_results: None,
             results_path: None,
+            mode: None,
+            words: None,
+            quote_length: None,
+            quotes_path: None,
+            text: None,
+            keep_newlines: None,
+            code_language: None,
+            code_path: None,
+            skip_indentation: None,
+            keyboard_layout: None,
+            adaptive: None,
+            lesson_target_wpm: None,
+            lesson_target_accuracy: None,
+            seed: None,
+            language: None,
+            list_languages: false,
+            min_word_length: None,
+            max_word_length: None,
+            exclude_words: None,
+            generator: None,
+            corpus_path: None,
+            punctuation: None,
+            sentence_end_ratio: None,
+            comma_ratio: None,
+            apostrophe_ratio: None,
+            quote_ratio: None,
+            number_formats: None,
+            ending_symbols: None,
+            surrounding_symbols: None,
+            max_width: None,
+            live_stats: None,
+            focus: None,
+            type_to_start: None,
             history: None,
         };
 
         let (config, expected_input, mut terminal) = setup_terminal(args)?;
 
-        let time_left = config.duration;
-        let mut app = Runner::new(config, expected_input);
+        let mut app = Runner::new(config, Box::new(expected_input));
 
         terminal
             .draw(|f: &mut Frame| {
                 let mut frame_wrapper = FrameWrapper::new(f);
-                app.render(&mut frame_wrapper, time_left.as_secs());
+                app.render(&mut frame_wrapper, Duration::from_secs(0));
             })
             .context("Unable to draw in terminal")?;
 
@@ -314,7 +656,7 @@ mod tests {
 
         let predicate = predicates::str::contains("hello world");
 
-        assert_eq!(true, predicate.eval(&text));
+        assert!(predicate.eval(&text));
 
         Ok(())
     }
@@ -333,28 +675,62 @@ mod tests {
             config_path: None,
             save_results: None,
             results_path: None,
+            mode: None,
+            words: None,
+            quote_length: None,
+            quotes_path: None,
+            text: None,
+            keep_newlines: None,
+            code_language: None,
+            code_path: None,
+            skip_indentation: None,
+            keyboard_layout: None,
+            adaptive: None,
+            lesson_target_wpm: None,
+            lesson_target_accuracy: None,
+            seed: None,
+            language: None,
+            list_languages: false,
+            min_word_length: None,
+            max_word_length: None,
+            exclude_words: None,
+            generator: None,
+            corpus_path: None,
+            punctuation: None,
+            sentence_end_ratio: None,
+            comma_ratio: None,
+            apostrophe_ratio: None,
+            quote_ratio: None,
+            number_formats: None,
+            ending_symbols: None,
+            surrounding_symbols: None,
+            max_width: None,
+            live_stats: None,
+            focus: None,
+            type_to_start: None,
             history: None,
         };
 
         let (config, expected_input, mut terminal) = setup_terminal(args)?;
 
-        let mut app = Runner::new(config, expected_input);
+        let mut app = Runner::new(config, Box::new(expected_input));
         let start_time = Instant::now();
 
         terminal
             .draw(|f: &mut Frame| {
                 let mut frame_wrapper = FrameWrapper::new(f);
-                app.render(&mut frame_wrapper, start_time.elapsed().as_secs());
+                app.render(&mut frame_wrapper, start_time.elapsed());
             })
             .context("Unable to draw in terminal")?;
 
         let text = extract_text_from_buffer(terminal.backend().buffer())
             .context("Unable to extract text from buffer")?;
 
-        let predicate =
-            predicates::str::contains("press 's' to start the test, 'q' to quit, '?' for help");
+        let predicate = predicates::str::contains(
+            "press 's' to start the test, 'o' for settings, 'q' to quit, '?' for help",
+        );
 
-        assert_eq!(true, predicate.eval(&text));
+        assert!(predicate.eval(&text));
 
         Ok(())
     }
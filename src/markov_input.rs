@@ -0,0 +1,268 @@
+//! Module with the expected input generated by a Markov chain
+//!
+//! The model is trained on a corpus file from `corpus_path` in config, or on the builtin corpus.
+//! Corpus is split into sentences, which end with a word ending with `.`, `!` or `?`,
+//! and for every word and every pair of consecutive words the model remembers the words
+//! following them in the corpus.
+//!
+//! Text is generated sentence by sentence. Each sentence starts with a word that starts a sentence
+//! in the corpus, capitalized, and next words are picked randomly from the words following
+//! the previous two words. If the previous two words have only one possible continuation,
+//! words following the previous word are used instead, so the text doesn't just copy the corpus.
+//! Sentences end where they end in the corpus, so punctuation lands in natural places.
+//!
+//! Like dictionary words, sentences are generated lazily and cached.
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use rand::{rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+
+use crate::config::Config;
+use crate::corpus;
+use crate::expected_input::ExpectedInputInterface;
+use crate::helpers::{get_chars_range, get_rng};
+
+/// Sentences longer than this amount of words are ended with a full stop
+const MAX_SENTENCE_WORDS_COUNT: usize = 30;
+
+/// Enum used in config for choosing how the text is generated in `time` and `words` modes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Generator {
+    /// random words sampled from the dictionary
+    #[default]
+    Words,
+    /// sentences generated by a Markov chain trained on the corpus
+    Markov,
+}
+
+/// Words following each word and each pair of words in the corpus
+#[derive(Debug, Default)]
+struct MarkovModel {
+    sentence_starts: Vec<String>,
+    transitions: HashMap<String, Vec<String>>,
+    pair_transitions: HashMap<(String, String), Vec<String>>,
+}
+
+impl MarkovModel {
+    fn new(corpus: &str) -> Self {
+        let mut model = Self::default();
+
+        for sentence in split_sentences(corpus) {
+            model.sentence_starts.push(sentence[0].to_string());
+            for window in sentence.windows(2) {
+                model
+                    .transitions
+                    .entry(window[0].to_string())
+                    .or_default()
+                    .push(window[1].to_string());
+            }
+            for window in sentence.windows(3) {
+                model
+                    .pair_transitions
+                    .entry((window[0].to_string(), window[1].to_string()))
+                    .or_default()
+                    .push(window[2].to_string());
+            }
+        }
+
+        model
+    }
+
+    /// Returns the words that can follow the previous words
+    fn get_next_words(&self, previous_word: &str, word: &str) -> Option<&Vec<String>> {
+        let pair_next_words = self
+            .pair_transitions
+            .get(&(previous_word.to_string(), word.to_string()));
+
+        match pair_next_words {
+            Some(next_words) if next_words.iter().any(|next| next != &next_words[0]) => {
+                Some(next_words)
+            }
+            _ => self.transitions.get(word).or(pair_next_words),
+        }
+    }
+}
+
+/// Splits the corpus into sentences of words
+fn split_sentences(corpus: &str) -> Vec<Vec<&str>> {
+    let mut sentences = vec![];
+    let mut sentence = vec![];
+
+    for word in corpus.split_whitespace() {
+        sentence.push(word);
+        if is_sentence_end(word) {
+            sentences.push(sentence);
+            sentence = vec![];
+        }
+    }
+    if !sentence.is_empty() {
+        sentences.push(sentence);
+    }
+
+    sentences
+}
+
+/// Checks if the word ends a sentence, also when it's followed by a closing quote or bracket
+fn is_sentence_end(word: &str) -> bool {
+    word.trim_end_matches(['"', '\'', ')'])
+        .ends_with(['.', '!', '?'])
+}
+
+/// Struct used by runner to hold sentences generated by the Markov chain
+#[derive(Debug)]
+pub struct ExpectedMarkov {
+    model: MarkovModel,
+    rng: StdRng,
+    chars: Vec<char>,
+}
+
+impl ExpectedMarkov {
+    /// Create new struct instance with the model trained on the corpus file or the builtin corpus
+    pub fn new(config: &Config) -> Result<Self> {
+        let corpus = match &config.corpus_path {
+            Some(corpus_path) => {
+                let mut str = String::new();
+                let mut file =
+                    std::fs::File::open(corpus_path).context("Unable to open corpus file")?;
+                file.read_to_string(&mut str)
+                    .context("Unable to read corpus file")?;
+                str
+            }
+            None => corpus::CORPUS.to_string(),
+        };
+
+        let model = MarkovModel::new(&corpus);
+        if model.sentence_starts.is_empty() {
+            bail!("Corpus is empty");
+        }
+
+        Ok(Self {
+            model,
+            rng: get_rng(config.seed),
+            chars: vec![],
+        })
+    }
+
+    /// Generates next sentence and appends it to generated characters
+    fn push_sentence(&mut self) {
+        let first_word = self
+            .model
+            .sentence_starts
+            .choose(&mut self.rng)
+            .expect("Model has sentence starts");
+        let mut sentence = vec![capitalize(first_word)];
+        let (mut previous_word, mut word) = (String::new(), first_word.clone());
+
+        while !is_sentence_end(&word) {
+            let next_word = self
+                .model
+                .get_next_words(&previous_word, &word)
+                .and_then(|next_words| next_words.choose(&mut self.rng));
+
+            match next_word {
+                Some(next_word) if sentence.len() < MAX_SENTENCE_WORDS_COUNT => {
+                    sentence.push(next_word.clone());
+                    previous_word = word;
+                    word = next_word.clone();
+                }
+                _ => {
+                    let last_word = sentence.pop().unwrap_or_default();
+                    sentence.push(format!("{}.", last_word.trim_end_matches([',', ';', ':'])));
+                    break;
+                }
+            }
+        }
+
+        if !self.chars.is_empty() {
+            self.chars.push(' ');
+        }
+        self.chars.extend(sentence.join(" ").chars());
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first_char) => first_char.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+impl ExpectedInputInterface for ExpectedMarkov {
+    /// Returns generated characters from the given range
+    ///
+    /// If not enough characters were generated yet, more sentences are generated and cached.
+    fn get_range(&mut self, start: usize, end: usize) -> String {
+        while self.chars.len() < end {
+            self.push_sentence();
+        }
+
+        get_chars_range(&self.chars, start, end)
+    }
+
+    /// Sentences are generated endlessly so there is no end of the expected input
+    fn get_len(&self) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn should_generate_sentences_from_corpus() {
+        let mut corpus_file = tempfile::NamedTempFile::new().expect("Unable to create temp file");
+        corpus_file
+            .write_all("the cat sat on the mat. a dog sat on the rug! where is the cat".as_bytes())
+            .expect("Unable to write to temp file");
+        let config = Config {
+            corpus_path: Some(corpus_file.path().to_path_buf()),
+            seed: Some(1),
+            ..Config::default()
+        };
+
+        let mut expected_markov =
+            ExpectedMarkov::new(&config).expect("Unable to create expected markov");
+        let str = expected_markov.get_range(0, 1000);
+        let corpus_words = [
+            "the", "cat", "sat", "on", "mat", "a", "dog", "rug", "where", "is",
+        ];
+
+        assert_eq!(str.chars().count(), 1000);
+        assert!(str.starts_with(['T', 'A', 'W']));
+        for word in str.split_whitespace() {
+            let word = word.trim_end_matches(['.', '!']).to_lowercase();
+            assert!(corpus_words.contains(&word.as_str()), "{}", word);
+        }
+        // every sentence but the cut last one ends with punctuation and starts capitalized
+        for sentence in str
+            .split_inclusive(['.', '!'])
+            .skip(1)
+            .map(str::trim_start)
+            .filter(|sentence| !sentence.is_empty())
+        {
+            assert!(sentence.starts_with(char::is_uppercase));
+        }
+    }
+
+    #[test]
+    fn should_split_corpus_into_sentences() {
+        let sentences = split_sentences("Hi there. \"Who are you?\" I asked\n politely");
+
+        assert_eq!(
+            sentences,
+            vec![
+                vec!["Hi", "there."],
+                vec!["\"Who", "are", "you?\""],
+                vec!["I", "asked", "politely"],
+            ]
+        );
+    }
+}
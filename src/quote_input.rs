@@ -0,0 +1,171 @@
+//! Module creating the expected input for the test runner in `quote` mode
+//!
+//! It picks a random quote from the builtin quotes collection,
+//! or from the quotes file if `quotes_path` is provided in config,
+//! filters the quotes by `quote_length`
+//! and returns the chosen quote verbatim, without shuffling or repeating it.
+//!
+//! Quotes file should be a JSON file with an array of quotes:
+//!
+//! ```json
+//! [
+//!     { "text": "It was the best of times, it was the worst of times.", "source": "Charles Dickens" }
+//! ]
+//! ```
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use rand::seq::IteratorRandom;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+use crate::config::Config;
+use crate::expected_input::ExpectedInputInterface;
+use crate::helpers::{get_chars_range, get_rng};
+use crate::quotes;
+
+/// Enum used in config for filtering quotes by the amount of their characters.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteLength {
+    #[default]
+    All,
+    Short,
+    Medium,
+    Long,
+}
+
+impl QuoteLength {
+    /// Checks if quote with given amount of characters falls into this length category
+    fn matches(&self, quote_len: usize) -> bool {
+        match self {
+            QuoteLength::All => true,
+            QuoteLength::Short => quote_len <= 100,
+            QuoteLength::Medium => (101..=300).contains(&quote_len),
+            QuoteLength::Long => quote_len > 300,
+        }
+    }
+}
+
+/// Used by `serde` crate to parse quotes file into a rust struct
+#[derive(Deserialize, Debug)]
+struct QuotesFileEntry {
+    pub text: String,
+    pub source: Option<String>,
+}
+
+/// Struct used by runner to hold the quote used for validation and as a placeholder
+#[derive(Debug)]
+pub struct ExpectedQuote {
+    pub id: usize,
+    pub source: String,
+    text: Vec<char>,
+}
+
+impl ExpectedQuote {
+    /// Create new struct instance by picking a random quote matching `quote_length` from config
+    ///
+    /// Id of the quote is its index in the builtin collection or in the quotes file.
+    /// Whitespace in the quote is normalized to single spaces.
+    pub fn new(config: &Config) -> Result<Self> {
+        let quotes: Vec<(String, String)> = match &config.quotes_path {
+            Some(quotes_path) => {
+                let mut str = String::new();
+                let mut file =
+                    std::fs::File::open(quotes_path).context("Unable to open quotes file")?;
+                file.read_to_string(&mut str)
+                    .context("Unable to read quotes file")?;
+
+                let entries: Vec<QuotesFileEntry> =
+                    serde_json::from_str(&str).context("Unable to parse quotes file")?;
+
+                entries
+                    .into_iter()
+                    .map(|entry| (entry.text, entry.source.unwrap_or_default()))
+                    .collect()
+            }
+            None => quotes::QUOTES
+                .iter()
+                .map(|quote| (quote.text.to_string(), quote.source.to_string()))
+                .collect(),
+        };
+
+        let (id, (text, source)) = quotes
+            .into_iter()
+            .map(|(text, source)| {
+                (
+                    text.split_whitespace().collect::<Vec<_>>().join(" "),
+                    source,
+                )
+            })
+            .enumerate()
+            .filter(|(_, (text, _))| {
+                !text.is_empty() && config.quote_length.matches(text.chars().count())
+            })
+            .choose(&mut get_rng(config.seed))
+            .context("Unable to find a quote matching the quote length")?;
+
+        Ok(Self {
+            id,
+            source,
+            text: text.chars().collect(),
+        })
+    }
+}
+
+impl ExpectedInputInterface for ExpectedQuote {
+    /// Returns characters of the quote from the given range
+    ///
+    /// If the quote ends before the end of the range, the rest of the quote is returned.
+    fn get_range(&mut self, start: usize, end: usize) -> String {
+        get_chars_range(&self.text, start, end)
+    }
+
+    fn get_len(&self) -> Option<usize> {
+        Some(self.text.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn should_pick_builtin_quote_matching_length() {
+        let config = Config {
+            quote_length: QuoteLength::Long,
+            ..Config::default()
+        };
+        let mut expected_quote =
+            ExpectedQuote::new(&config).expect("unable to create expected quote");
+
+        assert!(expected_quote.get_len().unwrap() > 300);
+        assert_eq!(
+            expected_quote.get_range(0, usize::MAX),
+            quotes::QUOTES[expected_quote.id].text
+        );
+    }
+
+    #[test]
+    fn should_read_quotes_file_and_normalize_whitespace() {
+        let mut quotes_file = tempfile::NamedTempFile::new().expect("Unable to create temp file");
+        quotes_file
+            .write_all(r#"[{ "text": "foo  bar\n baz", "source": "qux" }]"#.as_bytes())
+            .expect("Unable to write to temp file");
+        let config = Config {
+            quotes_path: Some(quotes_file.path().to_path_buf()),
+            ..Config::default()
+        };
+
+        let mut expected_quote =
+            ExpectedQuote::new(&config).expect("unable to create expected quote");
+
+        assert_eq!(expected_quote.id, 0);
+        assert_eq!(expected_quote.source, "qux");
+        assert_eq!(expected_quote.get_range(0, 100), "foo bar baz");
+        assert_eq!(expected_quote.get_range(0, 3), "foo");
+        assert_eq!(expected_quote.get_len(), Some(11));
+    }
+}
@@ -0,0 +1,70 @@
+//! module with quotes used to generate expected input in `quote` mode
+
+/// Single passage of text with information about where it comes from.
+pub struct Quote {
+    pub text: &'static str,
+    pub source: &'static str,
+}
+
+pub const QUOTES: &[Quote] = &[
+    Quote {
+        text: "It was the best of times, it was the worst of times.",
+        source: "Charles Dickens, A Tale of Two Cities",
+    },
+    Quote {
+        text: "All happy families are alike; each unhappy family is unhappy in its own way.",
+        source: "Leo Tolstoy, Anna Karenina",
+    },
+    Quote {
+        text: "Beware; for I am fearless, and therefore powerful.",
+        source: "Mary Shelley, Frankenstein",
+    },
+    Quote {
+        text: "Whatever our souls are made of, his and mine are the same.",
+        source: "Emily Bronte, Wuthering Heights",
+    },
+    Quote {
+        text: "I am no bird; and no net ensnares me: I am a free human being with an independent will.",
+        source: "Charlotte Bronte, Jane Eyre",
+    },
+    Quote {
+        text: "Very little is needed to make a happy life; it is all within yourself, in your way of thinking.",
+        source: "Marcus Aurelius, Meditations",
+    },
+    Quote {
+        text: "It is a truth universally acknowledged, that a single man in possession of a good fortune, must be in want of a wife.",
+        source: "Jane Austen, Pride and Prejudice",
+    },
+    Quote {
+        text: "A foolish consistency is the hobgoblin of little minds, adored by little statesmen and philosophers and divines.",
+        source: "Ralph Waldo Emerson, Self-Reliance",
+    },
+    Quote {
+        text: "Four score and seven years ago our fathers brought forth on this continent, a new nation, conceived in Liberty, and dedicated to the proposition that all men are created equal.",
+        source: "Abraham Lincoln, Gettysburg Address",
+    },
+    Quote {
+        text: "'Would you tell me, please, which way I ought to go from here?' 'That depends a good deal on where you want to get to,' said the Cat.",
+        source: "Lewis Carroll, Alice's Adventures in Wonderland",
+    },
+    Quote {
+        text: "I went to the woods because I wished to live deliberately, to front only the essential facts of life, and see if I could not learn what it had to teach, and not, when I came to die, discover that I had not lived.",
+        source: "Henry David Thoreau, Walden",
+    },
+    Quote {
+        text: "Call me Ishmael. Some years ago - never mind how long precisely - having little or no money in my purse, and nothing particular to interest me on shore, I thought I would sail about a little and see the watery part of the world.",
+        source: "Herman Melville, Moby-Dick",
+    },
+    Quote {
+        text: "It was the best of times, it was the worst of times, it was the age of wisdom, it was the age of foolishness, it was the epoch of belief, it was the epoch of incredulity, it was the season of Light, it was the season of Darkness, it was the spring of hope, it was the winter of despair, we had everything before us, we had nothing before us, we were all going direct to Heaven, we were all going direct the other way.",
+        source: "Charles Dickens, A Tale of Two Cities",
+    },
+    Quote {
+        text: "But, in a larger sense, we can not dedicate - we can not consecrate - we can not hallow - this ground. The brave men, living and dead, who struggled here, have consecrated it, far above our poor power to add or detract. The world will little note, nor long remember what we say here, but it can never forget what they did here.",
+        source: "Abraham Lincoln, Gettysburg Address",
+    },
+    Quote {
+        text: "Our life is frittered away by detail. An honest man has hardly need to count more than his ten fingers, or in extreme cases he may add his ten toes, and lump the rest. Simplicity, simplicity, simplicity! I say, let your affairs be as two or three, and not a hundred or a thousand; instead of a million count half a dozen, and keep your accounts on your thumb-nail.",
+        source: "Henry David Thoreau, Walden",
+    },
+];
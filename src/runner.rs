@@ -2,17 +2,42 @@
 //!
 //! This is the main module that is orchestrating the flow of the test.
 //! It prints the expected input as placeholder, then it reads user input and reacts to it.
-//! When program is started `Normal` mode is turned on.
-//! To go to `Editing` mode user needs to press `e`.
-//! To go to `Normal` mode from `Editing` mode, and effectively pause the test, press `<Esc>`.
+//! When program is started `Normal` mode is turned on, in which keys trigger actions.
+//! To go to `Editing` mode, where keys are typed, user needs to press the `start` key
+//! (`s` by default) and the test starts.
+//! With `type_to_start` the program starts in `Editing` mode instead, and the test starts
+//! with the first typed character, which is counted as input.
+//! To go to `Normal` mode from `Editing` mode, and effectively pause the test, press the `pause`
+//! key (`<Esc>` by default), the `start` key resumes the test.
+//! Keys of all actions can be changed in config, see the `keybindings` module.
+//! To restart the test with a new text press `r` in `Normal` mode or `<Ctrl+r>` in `Editing` mode.
+//! To change options of the test press `o` in `Normal` mode, which opens the settings window,
+//! and every change restarts the test with changed options.
 //!
 //! When a test is started it checks the user input
 //! and prints it to indicate valid characters and mistakes.
-//! After the `duration` (amount of seconds) specified in config has passed the test is finished.
-//! And test statistics are returned from the runner.
+//! In `time` mode the test is finished after the `duration` (amount of seconds) specified in config
+//! has passed.
+//! In `words` mode the test is finished once the user has typed the amount of `words` specified in
+//! config.
+//! In `quote` mode the test is finished once the user has typed the whole quote.
+//! In `text` mode the test is finished once the user has typed the whole text or the `duration`
+//! has passed, whichever comes first.
+//! In `code` mode the test is finished once the user has typed the whole code snippet.
+//! In `zen` mode there is no expected input and no time limit,
+//! the test is finished once the user presses the `finish` key (`<Enter>` by default).
+//! In `lesson` mode the test is finished once the user has typed all generated words.
+//!
+//! When line breaks of the expected input have to be typed (`code` mode, or `text` mode with
+//! `keep_newlines`) the expected input is rendered line by line and `<Enter>` types a line break.
+//!
+//! Once the test is finished its statistics are returned from the runner, together with the log
+//! of every key press, pause and resume of the test.
+//! Time of the test is measured with the `test_clock` module, so speed is counted only from
+//! the time the user was actually typing, without pauses.
 
 use anyhow::{Context, Result};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use mockall::automock;
 use ratatui::layout::Position;
 use ratatui::text::Text;
@@ -21,8 +46,17 @@ use std::time::{Duration, Instant};
 use crate::config::Config;
 use crate::expected_input::ExpectedInputInterface;
 use crate::help_window::HelpWindow;
-use crate::helpers::split_by_char_index;
-use crate::test_results::{Stats, TestResults};
+use crate::helpers::{
+    count_finished_words, get_line_len, get_words_end_index, split_by_char_index,
+};
+use crate::key_stats::KeysStats;
+use crate::keybindings::{Action, KeyContext};
+use crate::keystrokes::{KeystrokeEvent, KeystrokeEventKind};
+use crate::live_stats::{LiveStat, LiveStats};
+use crate::settings_window::SettingsWindow;
+use crate::test_clock::TestClock;
+use crate::test_mode::TestMode;
+use crate::test_results::{LineStats, Stats, TestResults};
 use ratatui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -31,13 +65,43 @@ use ratatui::{
     Frame, Terminal,
 };
 
-/// To switch from Normal to Editing press `e`.
-/// To switch from Editing to Normal press `<Esc>`.
+/// Symbol displayed in place of line breaks of the expected input
+const NEWLINE_SYMBOL: &str = "⏎";
+
+/// Maximum amount of spaces typed with `<Tab>` in `code` mode
+const TAB_WIDTH: usize = 4;
+
+/// Amount of lines of expected input visible at once
+const VISIBLE_LINES_COUNT: usize = 3;
+
+/// Row of the visible lines on which the current line stays once the text is scrolled
+const CURRENT_LINE_ROW: usize = 1;
+
+/// Test starts in Normal mode, where keys trigger actions like quitting or opening settings,
+/// and the start key switches to Editing mode, where keys are typed.
+/// The pause key switches from Editing back to Normal mode.
+///
+/// With `type_to_start` the test starts in Editing mode instead, and the timer is started
+/// by the first typed character. Pausing before that switches to Normal mode as usual,
+/// and the start key returns to Editing mode still waiting for the first character.
 enum InputMode {
     Normal,
     Editing,
 }
 
+/// How the test run by the runner has ended
+#[derive(Debug)]
+pub enum TestOutcome {
+    /// test was finished or quit, results tell if it was completed
+    Finished(Box<TestResults>),
+    /// test was restarted by the user, the next test is run with `config`, which differs
+    /// from config of this test if options were changed in the settings window
+    Restart {
+        config: Box<Config>,
+        settings_window: Option<SettingsWindow>,
+    },
+}
+
 /// Struct that runs and controls the test.
 pub struct Runner {
     input: String,
@@ -46,84 +110,314 @@ pub struct Runner {
     expected_input: Box<dyn ExpectedInputInterface>,
     raw_mistakes_count: u64,
     raw_valid_characters_count: u64,
-    is_started: bool,
+    last_keystroke_time: Option<Instant>,
+    keystroke_intervals: Vec<Duration>,
+    current_keystroke_interval: Option<Duration>,
+    keys_stats: KeysStats,
+    events: Vec<KeystrokeEvent>,
+    clock: TestClock,
     show_help: bool,
     help_window: HelpWindow,
+    show_settings: bool,
+    settings_window: SettingsWindow,
+    line_starts: Vec<usize>,
+    lines_width: usize,
+    expected_line_starts: Option<Vec<usize>>,
+    expected_words_count: Option<usize>,
+    live_stats: LiveStats,
 }
 
 impl Runner {
     /// Create new test runner instance
-    pub fn new(config: Config, expected_input: impl ExpectedInputInterface + 'static) -> Self {
+    pub fn new(config: Config, expected_input: Box<dyn ExpectedInputInterface>) -> Self {
+        let input_mode = match config.type_to_start {
+            true => InputMode::Editing,
+            false => InputMode::Normal,
+        };
+
         Self {
             input: String::new(),
-            input_mode: InputMode::Normal,
+            input_mode,
             config,
-            expected_input: Box::new(expected_input),
+            expected_input,
             raw_mistakes_count: 0,
             raw_valid_characters_count: 0,
-            is_started: false,
+            last_keystroke_time: None,
+            keystroke_intervals: vec![],
+            current_keystroke_interval: None,
+            keys_stats: KeysStats::default(),
+            events: vec![],
+            clock: TestClock::default(),
             show_help: false,
             help_window: HelpWindow::new(),
+            show_settings: false,
+            settings_window: SettingsWindow::new(),
+            line_starts: vec![0],
+            lines_width: 0,
+            expected_line_starts: None,
+            expected_words_count: None,
+            live_stats: LiveStats::default(),
+        }
+    }
+
+    /// Opens the settings window of the previous test on top of the test, if there is one
+    pub fn with_settings_window(mut self, settings_window: Option<SettingsWindow>) -> Self {
+        if let Some(settings_window) = settings_window {
+            self.settings_window = settings_window;
+            self.show_settings = true;
+            self.input_mode = InputMode::Normal;
         }
+
+        self
+    }
+
+    /// Takes back the expected input of the test, so the same text can be typed again
+    pub fn into_expected_input(self) -> Box<dyn ExpectedInputInterface> {
+        self.expected_input
     }
 
     /// Removes the last word from user input
+    ///
+    /// Whitespace preceding the removed word is kept, so line breaks typed by the user stay intact.
     fn remove_last_word(&mut self) {
-        let mut words = self.input.split_whitespace().collect::<Vec<&str>>();
-        words.pop();
+        let trimmed_input = self.input.trim_end();
+        let last_word_index = trimmed_input
+            .char_indices()
+            .rfind(|(_, c)| c.is_whitespace())
+            .map(|(index, c)| index + c.len_utf8())
+            .unwrap_or(0);
+
+        self.input.truncate(last_word_index);
+        self.live_stats.truncate(self.input.chars().count());
+    }
+
+    /// Adds typed character to user input and counts it as valid character or a mistake
+    ///
+    /// Returns true if the character was valid.
+    fn push_char(&mut self, c: char) -> bool {
+        self.input.push(c);
+
+        let input_chars_count = self.input.chars().count();
+        let expected_input_str = self
+            .expected_input
+            .get_range(input_chars_count.saturating_sub(2), input_chars_count);
+        let mut expected_chars = expected_input_str.chars();
+        let previous_expected_char = match input_chars_count {
+            1 => None,
+            _ => expected_chars.next(),
+        };
+        let expected_char = expected_chars.next();
+        // in `zen` mode there is nothing to compare with, so every character is valid
+        let is_correct = self.config.mode == TestMode::Zen || expected_char == Some(c);
+
+        if !is_correct {
+            self.raw_mistakes_count += 1;
+        } else {
+            self.raw_valid_characters_count += 1;
+        }
+        self.live_stats.push(is_correct);
+
+        if let Some(expected_char) = expected_char {
+            self.keys_stats.record(
+                expected_char,
+                previous_expected_char,
+                is_correct,
+                self.current_keystroke_interval,
+            );
+        }
+
+        is_correct
+    }
+
+    /// Saves time that has passed since the previous keystroke
+    ///
+    /// Time spent on a pause is not taken into consideration.
+    fn record_keystroke(&mut self) {
+        let now = Instant::now();
+        self.current_keystroke_interval = self
+            .last_keystroke_time
+            .map(|last_keystroke_time| now - last_keystroke_time);
+        if let Some(interval) = self.current_keystroke_interval {
+            self.keystroke_intervals.push(interval);
+        }
+        self.last_keystroke_time = Some(now);
+        self.clock.record_keystroke(now);
+    }
+
+    /// Adds an entry to the keystroke event log
+    fn record_event(&mut self, event: KeystrokeEvent) {
+        self.events.push(event);
+    }
+
+    /// Checks if line breaks of the expected input have to be typed with `<Enter>`
+    fn has_line_breaks(&self) -> bool {
+        match self.config.mode {
+            TestMode::Code => true,
+            TestMode::Text => self.config.keep_newlines,
+            TestMode::Time
+            | TestMode::Words
+            | TestMode::Quote
+            | TestMode::Zen
+            | TestMode::Lesson => false,
+        }
+    }
+
+    /// Returns the character of expected input that should be typed next
+    fn get_next_expected_char(&mut self) -> Option<char> {
+        let input_chars_count = self.input.chars().count();
 
-        self.input = words.join(" ");
+        self.expected_input
+            .get_range(input_chars_count, input_chars_count + 1)
+            .chars()
+            .next()
+    }
 
-        if !self.input.is_empty() {
+    /// Adds the indentation of the current line of expected input to user input
+    /// without counting it as typed characters
+    fn skip_indentation(&mut self) {
+        while self.get_next_expected_char() == Some(' ') {
             self.input.push(' ');
+            self.live_stats.push(true);
+        }
+    }
+
+    /// Types the indentation of the current line of expected input, up to `TAB_WIDTH` spaces
+    ///
+    /// If there is no indentation expected the tab character is typed, which counts as a mistake.
+    /// Returns true if the indentation was typed.
+    fn push_tab(&mut self) -> bool {
+        if self.get_next_expected_char() != Some(' ') {
+            return self.push_char('\t');
+        }
+
+        for _ in 0..TAB_WIDTH {
+            if self.get_next_expected_char() != Some(' ') {
+                break;
+            }
+            self.push_char(' ');
+        }
+
+        true
+    }
+
+    /// Returns the amount of characters user has to type to finish the test in `words` mode.
+    ///
+    /// It's the length of expected input up to the end of the last word of the test.
+    /// Expected input is read in chunks, so it's read only once however long the test is.
+    fn get_words_mode_input_len(&mut self) -> usize {
+        if self.config.words == 0 {
+            return 0;
+        }
+
+        let chunk_len = self.config.words * 10;
+        let mut words_left = self.config.words;
+        let mut start: usize = 0;
+
+        loop {
+            // chunks overlap by one character, so words ending between them are counted
+            let chunk_start = start.saturating_sub(1);
+            let chunk = self
+                .expected_input
+                .get_range(chunk_start, start + chunk_len);
+            if let Some(index) = get_words_end_index(&chunk, words_left) {
+                return chunk_start + index;
+            }
+            let chunk_chars_count = chunk.chars().count();
+            // expected input ended before the last word of the test
+            if chunk_start + chunk_chars_count < start + chunk_len {
+                return chunk_start + chunk_chars_count;
+            }
+            words_left -= count_finished_words(&chunk);
+            start += chunk_len;
+        }
+    }
+
+    /// Returns the whole expected input of a fixed length, or nothing if it's endless
+    fn get_whole_expected_input(&mut self) -> String {
+        let expected_input_len = self.expected_input.get_len().unwrap_or_default();
+
+        self.expected_input.get_range(0, expected_input_len)
+    }
+
+    /// Returns the index of the first character of each line of expected input
+    ///
+    /// Expected input doesn't change during the test, so lines are found only once.
+    fn get_expected_line_starts(&mut self) -> &[usize] {
+        if self.expected_line_starts.is_none() {
+            let mut line_starts = vec![0];
+            line_starts.extend(
+                self.get_whole_expected_input()
+                    .chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '\n')
+                    .map(|(index, _)| index + 1),
+            );
+            self.expected_line_starts = Some(line_starts);
+        }
+
+        self.expected_line_starts
+            .as_deref()
+            .expect("Line starts are found above")
+    }
+
+    /// Returns the amount of words of expected input of a fixed length, counted only once
+    fn get_expected_words_count(&mut self) -> usize {
+        match self.expected_words_count {
+            Some(words_count) => words_count,
+            None => {
+                let words_count = self.get_whole_expected_input().split_whitespace().count();
+                self.expected_words_count = Some(words_count);
+                words_count
+            }
         }
     }
 
     /// Method that runs the test.
     ///
     /// It renders the application using the `tui` crate and reacts to user input.
-    pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<TestResults> {
-        let mut start_time = Instant::now();
-        let mut pause_time = Instant::now();
+    pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<TestOutcome> {
         let tick_rate = Duration::from_secs(1);
         let mut last_tick = Instant::now();
+        let mut is_finished_by_user = false;
+        let time_limit = match self.config.mode {
+            TestMode::Time | TestMode::Text => Some(self.config.duration),
+            _ => None,
+        };
+        let input_len_limit = match self.config.mode {
+            TestMode::Time | TestMode::Zen => None,
+            TestMode::Words => Some(self.get_words_mode_input_len()),
+            TestMode::Quote | TestMode::Text | TestMode::Code | TestMode::Lesson => {
+                self.expected_input.get_len()
+            }
+        };
 
         loop {
+            let now = Instant::now();
             if let InputMode::Editing = self.input_mode {
-                if self.is_started && start_time.elapsed() >= self.config.duration {
-                    return Ok(TestResults::new(
-                        self.get_stats(),
+                let is_time_up =
+                    time_limit.is_some_and(|time_limit| self.clock.elapsed(now) >= time_limit);
+                let is_input_finished = input_len_limit
+                    .is_some_and(|input_len_limit| self.input.chars().count() >= input_len_limit);
+
+                if self.clock.is_started()
+                    && (is_time_up || is_input_finished || is_finished_by_user)
+                {
+                    let active_time = self.clock.get_active_time(now, time_limit);
+
+                    return Ok(TestOutcome::Finished(Box::new(TestResults::new(
+                        self.get_stats(active_time),
                         self.config.clone(),
                         true,
-                    ));
+                    ))));
                 }
             }
 
-            let time_left = match self.input_mode {
-                InputMode::Normal => match self.is_started {
-                    false => self.config.duration,
-                    true => self
-                        .config
-                        .duration
-                        .checked_sub(
-                            start_time
-                                .elapsed()
-                                .checked_sub(pause_time.elapsed())
-                                .unwrap_or(Duration::from_secs(0)),
-                        )
-                        .unwrap_or(Duration::from_secs(0)),
-                },
-                InputMode::Editing => self
-                    .config
-                    .duration
-                    .checked_sub(start_time.elapsed())
-                    .unwrap_or(Duration::from_secs(0)),
-            };
+            let elapsed = self.clock.elapsed(now);
 
             terminal
                 .draw(|f: &mut Frame| {
                     let mut frame_wrapper = FrameWrapper::new(f);
-                    self.render(&mut frame_wrapper, time_left.as_secs());
+                    self.render(&mut frame_wrapper, elapsed);
                 })
                 .context("Unable to draw in terminal")?;
 
@@ -135,78 +429,185 @@ impl Runner {
                 if let Event::Key(key) = event::read().context("Unable to read event")? {
                     if key.kind == KeyEventKind::Press {
                         match self.input_mode {
+                            InputMode::Normal if self.show_settings => match self
+                                .config
+                                .keybindings
+                                .get_action(&key, KeyContext::Settings)
+                            {
+                                Some(Action::Settings | Action::Close) => {
+                                    self.show_settings = false;
+                                }
+                                Some(Action::SelectPrevious) => {
+                                    self.settings_window.select_previous();
+                                }
+                                Some(Action::SelectNext) => {
+                                    self.settings_window.select_next();
+                                }
+                                Some(Action::PreviousValue) => {
+                                    return Ok(self.change_setting(false));
+                                }
+                                Some(Action::NextValue) => {
+                                    return Ok(self.change_setting(true));
+                                }
+                                Some(Action::SaveSettings) => {
+                                    self.config
+                                        .save_settings()
+                                        .context("Unable to save settings")?;
+                                }
+                                _ => {}
+                            },
                             InputMode::Normal => match self.show_help {
-                                true => match key.code {
-                                    KeyCode::Char('?') => {
+                                true => {
+                                    if self.config.keybindings.get_action(&key, KeyContext::Normal)
+                                        == Some(Action::Help)
+                                    {
                                         self.show_help = false;
                                     }
-                                    _ => {}
-                                },
-                                false => match key.code {
-                                    KeyCode::Char('s') => {
-                                        start_time = if self.is_started {
-                                            start_time + pause_time.elapsed()
-                                        } else {
-                                            Instant::now()
-                                        };
-                                        self.is_started = true;
+                                }
+                                false => match self
+                                    .config
+                                    .keybindings
+                                    .get_action(&key, KeyContext::Normal)
+                                {
+                                    // with `type_to_start` the timer waits for the first character
+                                    Some(Action::Start)
+                                        if !self.clock.is_started()
+                                            && self.config.type_to_start =>
+                                    {
+                                        self.input_mode = InputMode::Editing;
+                                    }
+                                    Some(Action::Start) => {
+                                        let now = Instant::now();
+                                        if self.clock.is_started() {
+                                            self.clock.resume(now);
+                                            self.record_event(KeystrokeEvent::new(
+                                                self.clock.elapsed(now),
+                                                KeystrokeEventKind::Resume,
+                                            ));
+                                        }
+                                        self.clock.start(now);
                                         self.input_mode = InputMode::Editing;
                                     }
-                                    KeyCode::Char('q') => {
+                                    Some(Action::Quit) => {
                                         // TODO: return canceled test error and handle it in main
-                                        return Ok(TestResults::new(
-                                            Stats::default(),
-                                            self.config.clone(),
-                                            false,
-                                        ));
+                                        return Ok(TestOutcome::Finished(Box::new(
+                                            TestResults::new(
+                                                Stats::default(),
+                                                self.config.clone(),
+                                                false,
+                                            ),
+                                        )));
                                     }
-                                    KeyCode::Char('?') => {
+                                    Some(Action::Restart) => {
+                                        return Ok(self.get_restart_outcome());
+                                    }
+                                    // options of the daily challenge can't be changed
+                                    Some(Action::Settings) if !self.config.daily => {
+                                        self.show_settings = true;
+                                    }
+                                    Some(Action::Help) => {
                                         self.show_help = true;
                                     }
                                     _ => {}
                                 },
                             },
-                            InputMode::Editing => match key.code {
-                                // Crossterm returns `ctrl+w` or ``ctrl+h` when `ctrl+backspace` is pressed
-                                // see: https://github.com/crossterm-rs/crossterm/issues/504
-                                KeyCode::Char('h') | KeyCode::Char('w')
-                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
-                                {
-                                    self.remove_last_word();
-                                }
-                                KeyCode::Char(c) => {
-                                    self.input.push(c);
-
-                                    let expected_input = self
-                                        .expected_input
-                                        .get_string(self.input.len())
-                                        .chars()
-                                        .collect::<Vec<char>>();
-
-                                    let is_correct =
-                                        self.input.chars().last() == expected_input.last().copied();
-
-                                    if !is_correct {
-                                        self.raw_mistakes_count += 1;
-                                    } else {
-                                        self.raw_valid_characters_count += 1;
+                            InputMode::Editing => {
+                                let action = self
+                                    .config
+                                    .keybindings
+                                    .get_action(&key, KeyContext::Editing);
+
+                                if !self.clock.is_started() {
+                                    match (action, key.code) {
+                                        (Some(Action::Pause), _) => {
+                                            self.input_mode = InputMode::Normal;
+                                            continue;
+                                        }
+                                        (Some(Action::Restart), _) => {
+                                            return Ok(self.get_restart_outcome());
+                                        }
+                                        // only a typed character starts the timer
+                                        (None, KeyCode::Char(_)) => {
+                                            self.clock.start(Instant::now());
+                                        }
+                                        _ => continue,
                                     }
                                 }
-                                KeyCode::Backspace
-                                    if key.modifiers.contains(KeyModifiers::ALT)
-                                        | key.modifiers.contains(KeyModifiers::CONTROL) =>
-                                {
-                                    self.remove_last_word();
-                                }
-                                KeyCode::Backspace => {
-                                    self.input.pop();
-                                }
-                                KeyCode::Esc => {
-                                    pause_time = Instant::now();
-                                    self.input_mode = InputMode::Normal;
+
+                                // only keys changing the input are counted as keystrokes
+                                match action {
+                                    Some(Action::Pause) => {
+                                        let now = Instant::now();
+                                        self.record_event(KeystrokeEvent::new(
+                                            self.clock.elapsed(now),
+                                            KeystrokeEventKind::Pause,
+                                        ));
+                                        self.clock.pause(now);
+                                        self.last_keystroke_time = None;
+                                        self.input_mode = InputMode::Normal;
+                                    }
+                                    Some(Action::Restart) => {
+                                        return Ok(self.get_restart_outcome());
+                                    }
+                                    Some(Action::WordDelete) => {
+                                        self.record_keystroke();
+                                        self.record_event(KeystrokeEvent::new(
+                                            self.clock.elapsed(Instant::now()),
+                                            KeystrokeEventKind::WordDelete,
+                                        ));
+                                        self.remove_last_word();
+                                    }
+                                    // in other modes the key is handled as usual, e.g. `<Enter>`
+                                    // types a line break
+                                    Some(Action::Finish) if self.config.mode == TestMode::Zen => {
+                                        is_finished_by_user = true;
+                                    }
+                                    _ => match key.code {
+                                        KeyCode::Char(c) => {
+                                            self.record_keystroke();
+                                            let is_correct = self.push_char(c);
+                                            self.record_event(KeystrokeEvent::char(
+                                                self.clock.elapsed(Instant::now()),
+                                                c,
+                                                is_correct,
+                                            ));
+                                        }
+                                        KeyCode::Enter if self.has_line_breaks() => {
+                                            self.record_keystroke();
+                                            let is_correct = self.push_char('\n');
+                                            self.record_event(KeystrokeEvent::char(
+                                                self.clock.elapsed(Instant::now()),
+                                                '\n',
+                                                is_correct,
+                                            ));
+                                            if self.config.mode == TestMode::Code
+                                                && self.config.skip_indentation
+                                            {
+                                                self.skip_indentation();
+                                            }
+                                        }
+                                        KeyCode::Tab if self.config.mode == TestMode::Code => {
+                                            self.record_keystroke();
+                                            let is_correct = self.push_tab();
+                                            self.record_event(KeystrokeEvent::char(
+                                                self.clock.elapsed(Instant::now()),
+                                                '\t',
+                                                is_correct,
+                                            ));
+                                        }
+                                        KeyCode::Backspace => {
+                                            self.record_keystroke();
+                                            self.record_event(KeystrokeEvent::new(
+                                                self.clock.elapsed(Instant::now()),
+                                                KeystrokeEventKind::Backspace,
+                                            ));
+                                            self.input.pop();
+                                            self.live_stats.truncate(self.input.chars().count());
+                                        }
+                                        _ => {}
+                                    },
                                 }
-                                _ => {}
-                            },
+                            }
                         }
                     }
                 }
@@ -218,24 +619,72 @@ impl Runner {
         }
     }
 
+    /// Changes the option selected in the settings window and restarts the test,
+    /// so that the test is generated again right away from the changed options
+    fn change_setting(&mut self, forward: bool) -> TestOutcome {
+        self.settings_window
+            .change_selected(&mut self.config, forward);
+        self.get_restart_outcome()
+    }
+
+    /// Outcome of the test restarted before it was finished, the settings window is reopened
+    /// in the next test if it's open
+    fn get_restart_outcome(&self) -> TestOutcome {
+        TestOutcome::Restart {
+            config: Box::new(self.config.clone()),
+            settings_window: self.show_settings.then(|| self.settings_window.clone()),
+        }
+    }
+
     /// Render a frame with each visual elements of the program in terminal.
     ///
-    /// There are two areas being rendered,
-    /// info area - where help message and time remaining is rendered.
-    /// and input area - where user input and expected input are displayed,
-    pub fn render(&mut self, frame: &mut impl FrameWrapperInterface, time_left: u64) {
+    /// There are three areas being rendered,
+    /// info area - where help message and time remaining is rendered,
+    /// stats area - where statistics chosen in `live_stats` are rendered, if there are any,
+    /// and input area - where user input and expected input are displayed.
+    /// In `focus` mode only the input area is rendered while the test is running.
+    pub fn render(&mut self, frame: &mut impl FrameWrapperInterface, elapsed: Duration) {
+        let is_focused = self.config.focus
+            && self.clock.is_started()
+            && matches!(self.input_mode, InputMode::Editing);
+        let info_area_height = match is_focused {
+            true => 0,
+            false => 1,
+        };
+        let stats_area_height = match is_focused || self.config.live_stats.is_empty() {
+            true => 0,
+            false => 1,
+        };
+
         // Calculate base layout first.
         let areas = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+            .constraints(
+                [
+                    Constraint::Length(info_area_height),
+                    Constraint::Length(stats_area_height),
+                    Constraint::Min(1),
+                ]
+                .as_ref(),
+            )
             .split(frame.area());
         let info_area = areas[0];
-        let input_area = areas[1];
+        let stats_area = areas[1];
+        let input_area = areas[2];
 
-        self.render_info_area(time_left, frame, info_area);
+        if info_area_height > 0 {
+            let time_left = self.config.duration.saturating_sub(elapsed);
+            self.render_info_area(time_left.as_secs(), frame, info_area);
+        }
+        if stats_area_height > 0 {
+            self.render_stats_area(elapsed, frame, stats_area);
+        }
 
-        let (current_line_index, input_current_line_len) =
-            self.render_expected_input_area(frame, input_area);
+        let (current_line_index, input_current_line_len) = match self.config.mode {
+            TestMode::Zen => self.render_zen_input_area(frame, input_area),
+            _ if self.has_line_breaks() => self.render_expected_lines_area(frame, input_area),
+            _ => self.render_expected_input_area(frame, input_area),
+        };
 
         self.move_cursor(
             frame,
@@ -246,66 +695,245 @@ impl Runner {
 
         // Then render help window on top if needed
         if self.show_help {
-            self.help_window.render(frame)
+            self.help_window.render(frame, &self.config)
+        }
+        if self.show_settings {
+            self.settings_window.render(frame, &self.config)
+        }
+    }
+
+    /// Breaks expected input into lines of the given `width` at word boundaries
+    /// and returns the index of the line containing the character typed next.
+    ///
+    /// Line breaks are computed once and cached, so lines don't change while the user types.
+    /// They are computed again only if the width changes, e.g. when the terminal is resized.
+    fn wrap_expected_input(&mut self, width: usize, input_chars_count: usize) -> usize {
+        if width != self.lines_width {
+            self.line_starts = vec![0];
+            self.lines_width = width;
+        }
+
+        loop {
+            let current_line_index = self
+                .line_starts
+                .partition_point(|line_start| *line_start <= input_chars_count)
+                - 1;
+            if self.line_starts.len() > current_line_index + VISIBLE_LINES_COUNT {
+                return current_line_index;
+            }
+
+            let line_start = *self.line_starts.last().expect("There is the first line");
+            let line = self
+                .expected_input
+                .get_range(line_start, line_start + width + 1);
+            // the rest of expected input fits in the last line
+            if line.chars().count() <= width {
+                return current_line_index;
+            }
+            self.line_starts
+                .push(line_start + get_line_len(&line, width));
         }
     }
 
+    /// Render expected input wrapped at word boundaries with user input printed over it.
+    ///
+    /// Only `VISIBLE_LINES_COUNT` lines are shown, and once the user gets past the first line
+    /// the text is scrolled, so the current line always stays on the same row.
+    /// Lines are at most `max_width` characters wide and centred in the area.
     fn render_expected_input_area(
         &mut self,
         frame: &mut impl FrameWrapperInterface,
         input_area: Rect,
     ) -> (u16, usize) {
-        let frame_width: usize = frame.area().width as usize;
-        let input_chars_count: usize = self.input.chars().count();
-        let current_line_index = (input_chars_count / frame_width) as u16;
-        let input_current_line_len = input_chars_count % frame_width;
+        let width = match self.config.max_width {
+            0 => input_area.width,
+            max_width => input_area.width.min(max_width),
+        }
+        .max(1);
+        let area = Rect {
+            x: input_area.x + (input_area.width.saturating_sub(width)) / 2,
+            width,
+            ..input_area
+        };
+        let input_chars_count = self.input.chars().count();
+        let current_line_index = self.wrap_expected_input(width as usize, input_chars_count);
+        let first_visible_line_index = current_line_index.saturating_sub(CURRENT_LINE_ROW);
+        let visible_lines_count = VISIBLE_LINES_COUNT.min(area.height as usize);
 
-        let expected_input_str = self
-            .expected_input
-            .get_string((current_line_index as usize + 2) * frame_width);
+        for line_index in
+            (first_visible_line_index..self.line_starts.len()).take(visible_lines_count)
+        {
+            let line_start = self.line_starts[line_index];
+            let line_end = self
+                .line_starts
+                .get(line_index + 1)
+                .copied()
+                .unwrap_or(line_start + width as usize);
+            let line = self.expected_input.get_range(line_start, line_end);
+            let typed_chars_count = input_chars_count
+                .saturating_sub(line_start)
+                .min(line.chars().count());
+            let (line_typed, line_rest) = split_by_char_index(&line, typed_chars_count);
+            let line_area = Rect {
+                y: area.y + (line_index - first_visible_line_index) as u16,
+                height: 1,
+                ..area
+            };
 
-        let (expected_input_current_line, expected_input_following_lines) = split_by_char_index(
-            &expected_input_str,
-            ((current_line_index as usize) + 1) * frame_width,
-        );
+            self.print_input(frame, line_typed, line_start, line_area);
 
-        let (expected_input_current_line_already_typed, expected_input_current_line_rest) =
-            split_by_char_index(&expected_input_current_line, input_chars_count);
+            self.print_block_of_text(
+                frame,
+                line_rest.replace('\n', NEWLINE_SYMBOL),
+                Rect {
+                    x: line_area.x + typed_chars_count as u16,
+                    width: line_area.width - typed_chars_count as u16,
+                    ..line_area
+                },
+                match line_index == current_line_index {
+                    true => Color::Gray,
+                    false => Color::DarkGray,
+                },
+                false,
+                false,
+            );
+        }
 
-        let expected_input_str = expected_input_current_line_already_typed.to_string()
-            + expected_input_current_line_rest
-            + expected_input_following_lines;
+        (
+            (current_line_index - first_visible_line_index) as u16,
+            (area.x - input_area.x) as usize + input_chars_count
+                - self.line_starts[current_line_index],
+        )
+    }
 
-        self.print_input(frame, &expected_input_str, input_area, frame_width);
+    /// Render user input split into lines of the area width, used when there is no expected input.
+    fn render_zen_input_area(
+        &mut self,
+        frame: &mut impl FrameWrapperInterface,
+        input_area: Rect,
+    ) -> (u16, usize) {
+        // there is no room for the input in a terminal this narrow
+        if input_area.width == 0 {
+            return (0, 0);
+        }
 
-        self.print_block_of_text(
-            frame,
-            expected_input_current_line_rest.to_string(),
-            Rect {
-                x: input_area.x + input_current_line_len as u16,
-                y: input_area.y + current_line_index,
-                width: frame_width as u16 - input_current_line_len as u16,
-                height: 1,
-            },
-            Color::Gray,
-            false,
-            false,
-        );
+        let width = input_area.width as usize;
+        let input_chars = self.input.chars().collect::<Vec<_>>();
+        let lines = input_chars.chunks(width).collect::<Vec<_>>();
+        let input_chars_count = input_chars.len();
+        let current_line_index = input_chars_count / width;
+        let first_visible_line_index =
+            current_line_index.saturating_sub(input_area.height.saturating_sub(1) as usize);
 
-        self.print_block_of_text(
-            frame,
-            expected_input_following_lines.to_string(),
-            Rect {
-                x: input_area.x,
-                y: input_area.y + current_line_index + 1,
-                height: input_area.height - current_line_index - 1,
-                width: input_area.width,
-            },
-            Color::DarkGray,
-            true,
-            false,
-        );
-        (current_line_index, input_current_line_len)
+        for (line_index, line) in lines.iter().enumerate().skip(first_visible_line_index) {
+            self.print_block_of_text(
+                frame,
+                line.iter().collect(),
+                Rect {
+                    x: input_area.x,
+                    y: input_area.y + (line_index - first_visible_line_index) as u16,
+                    width: input_area.width,
+                    height: 1,
+                },
+                self.config.colors.correct_match_fg,
+                false,
+                false,
+            );
+        }
+
+        (
+            (current_line_index - first_visible_line_index) as u16,
+            input_chars_count % width,
+        )
+    }
+
+    /// Render expected input preserving its line structure.
+    ///
+    /// Lines longer than the area are cut, and the view is scrolled to keep the current line
+    /// in the middle of the area.
+    fn render_expected_lines_area(
+        &mut self,
+        frame: &mut impl FrameWrapperInterface,
+        input_area: Rect,
+    ) -> (u16, usize) {
+        let input_chars_count = self.input.chars().count();
+        let line_starts = self.get_expected_line_starts();
+        let lines_count = line_starts.len();
+        let current_line_index =
+            line_starts.partition_point(|start| *start <= input_chars_count) - 1;
+        let current_column_index = input_chars_count - line_starts[current_line_index];
+        let visible_lines_count = input_area.height as usize;
+        let first_visible_line_index = current_line_index.saturating_sub(visible_lines_count / 2);
+        // starts of visible lines followed by the start of the next line, which ends the last one
+        let visible_line_starts = line_starts[first_visible_line_index
+            ..(first_visible_line_index + visible_lines_count + 1).min(lines_count)]
+            .to_vec();
+
+        for (row, line_start) in visible_line_starts
+            .iter()
+            .copied()
+            .enumerate()
+            .take(visible_lines_count)
+        {
+            let line_index = first_visible_line_index + row;
+            let is_last_line = line_index == lines_count - 1;
+            let line_end = match visible_line_starts.get(row + 1) {
+                // line break isn't part of the line, it's rendered with the newline symbol
+                Some(next_line_start) => next_line_start - 1,
+                None => self.expected_input.get_len().unwrap_or(line_start),
+            };
+            let line = self.expected_input.get_range(line_start, line_end);
+            let y = input_area.y + row as u16;
+
+            let mut rendered_line = line.clone();
+            if !is_last_line {
+                rendered_line.push_str(NEWLINE_SYMBOL);
+            }
+            self.print_block_of_text(
+                frame,
+                rendered_line,
+                Rect {
+                    x: input_area.x,
+                    y,
+                    width: input_area.width,
+                    height: 1,
+                },
+                match line_index == current_line_index {
+                    true => Color::Gray,
+                    false => Color::DarkGray,
+                },
+                false,
+                false,
+            );
+
+            let input_line = self
+                .input
+                .chars()
+                .skip(line_start)
+                .take(line.chars().count() + usize::from(!is_last_line))
+                .collect::<String>();
+            let expected_line = line.chars().chain(['\n']);
+            for (column_index, (input_char, expected_input_char)) in
+                input_line.chars().zip(expected_line).enumerate()
+            {
+                if column_index >= input_area.width as usize {
+                    break;
+                }
+
+                self.print_input_char(
+                    frame,
+                    input_char,
+                    expected_input_char,
+                    input_area.x + column_index as u16,
+                    y,
+                );
+            }
+        }
+
+        (
+            (current_line_index - first_visible_line_index) as u16,
+            current_column_index,
+        )
     }
 
     fn render_info_area(
@@ -314,75 +942,202 @@ impl Runner {
         frame: &mut impl FrameWrapperInterface,
         info_area: Rect,
     ) {
-        let label = match time_left {
-            1 => "second",
-            _ => "seconds",
+        let progress_message = match self.config.mode {
+            TestMode::Time | TestMode::Text => {
+                let label = match time_left {
+                    1 => "second",
+                    _ => "seconds",
+                };
+                format!("{} {label} left", time_left)
+            }
+            TestMode::Words => format!(
+                "{}/{}",
+                count_finished_words(&self.input).min(self.config.words),
+                self.config.words
+            ),
+            TestMode::Zen => {
+                let words_count = self.input.split_whitespace().count();
+                let label = match words_count {
+                    1 => "word",
+                    _ => "words",
+                };
+                format!("{} {label} typed", words_count)
+            }
+            TestMode::Quote | TestMode::Code | TestMode::Lesson => {
+                let words_count = self.get_expected_words_count();
+                format!(
+                    "{}/{}",
+                    count_finished_words(&self.input).min(words_count),
+                    words_count
+                )
+            }
         };
-        let time_left_message = format!("{} {label} left", time_left);
 
         self.print_block_of_text(
             frame,
-            time_left_message,
+            progress_message,
             info_area,
             Color::Yellow,
             true,
             false,
         );
 
+        let keybindings = &self.config.keybindings;
         let help_message = match self.input_mode {
             InputMode::Normal => {
-                if self.is_started {
-                    "press 's' to resume the test, 'q' to quit, '?' for help"
-                } else {
-                    "press 's' to start the test, 'q' to quit, '?' for help"
-                }
+                let action = match self.clock.is_started() {
+                    true => "resume",
+                    false => "start",
+                };
+                let settings_message = match self.config.daily {
+                    true => String::new(),
+                    false => format!(
+                        "'{}' for settings, ",
+                        keybindings.describe(Action::Settings)
+                    ),
+                };
+                format!(
+                    "press '{}' to {action} the test, {settings_message}'{}' to quit, '{}' for help",
+                    keybindings.describe(Action::Start),
+                    keybindings.describe(Action::Quit),
+                    keybindings.describe(Action::Help),
+                )
             }
-            InputMode::Editing => "press '<Esc>' to pause the test",
+            InputMode::Editing if !self.clock.is_started() => format!(
+                "start typing to begin the test, press '{}' for other options",
+                keybindings.describe(Action::Pause)
+            ),
+            InputMode::Editing => match self.config.mode {
+                TestMode::Zen => format!(
+                    "press '{}' to finish the test, '{}' to pause it",
+                    keybindings.describe(Action::Finish),
+                    keybindings.describe(Action::Pause)
+                ),
+                _ => format!(
+                    "press '{}' to pause the test",
+                    keybindings.describe(Action::Pause)
+                ),
+            },
         };
+        self.print_block_of_text(frame, help_message, info_area, Color::Yellow, true, true);
+    }
+
+    /// Render statistics chosen in `live_stats`, counted from the start of the test.
+    ///
+    /// Accuracy and errors count every key press, including the corrected mistakes.
+    fn render_stats_area(
+        &mut self,
+        elapsed: Duration,
+        frame: &mut impl FrameWrapperInterface,
+        stats_area: Rect,
+    ) {
+        let raw_typed_characters_count = self.raw_valid_characters_count + self.raw_mistakes_count;
+        let stats_message = self
+            .config
+            .live_stats
+            .iter()
+            .map(|live_stat| match live_stat {
+                LiveStat::Wpm => format!(
+                    "{:.0} wpm",
+                    get_wpm(self.live_stats.get_valid_characters_count() as u64, elapsed)
+                ),
+                LiveStat::RawWpm => {
+                    format!(
+                        "{:.0} raw wpm",
+                        get_wpm(raw_typed_characters_count, elapsed)
+                    )
+                }
+                LiveStat::Accuracy => format!(
+                    "{:.0}% accuracy",
+                    get_percentage(
+                        self.raw_valid_characters_count as f64,
+                        raw_typed_characters_count as f64
+                    )
+                ),
+                LiveStat::Errors => {
+                    let label = match self.raw_mistakes_count {
+                        1 => "error",
+                        _ => "errors",
+                    };
+                    format!("{} {label}", self.raw_mistakes_count)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+
         self.print_block_of_text(
             frame,
-            help_message.to_string(),
-            info_area,
+            stats_message,
+            stats_area,
             Color::Yellow,
-            true,
-            true,
+            false,
+            false,
         );
     }
 
-    /// Iterate over characters in user input
-    /// and print them using different colors indicating if they are valid or wrong.
+    /// Iterate over characters in user input, starting at `input_start` index,
+    /// and print them in a line of the area using different colors indicating
+    /// if they are valid or wrong.
     fn print_input(
         &mut self,
         frame: &mut impl FrameWrapperInterface,
         expected_input: &str,
-        input_area: Rect,
-        frame_width: usize,
+        input_start: usize,
+        area: Rect,
     ) {
-        for ((input_char_index, input_char), expected_input_char) in
-            self.input.chars().enumerate().zip(expected_input.chars())
+        for ((input_char_index, input_char), expected_input_char) in self
+            .input
+            .chars()
+            .skip(input_start)
+            .enumerate()
+            .zip(expected_input.chars())
+            .take(area.width as usize)
         {
-            let input: Paragraph<'_> = Paragraph::new(expected_input_char.to_string()).style(
-                match input_char == expected_input_char {
-                    true => Style::default()
-                        .bg(self.config.colors.correct_match_bg)
-                        .fg(self.config.colors.correct_match_fg),
-                    false => Style::default()
-                        .bg(self.config.colors.incorrect_match_bg)
-                        .fg(self.config.colors.incorrect_match_fg),
-                },
-            );
-            frame.render_widget(
-                input,
-                Rect {
-                    x: (input_area.x + input_char_index as u16) % frame_width as u16,
-                    y: input_area.y + input_char_index as u16 / frame_width as u16,
-                    width: 1,
-                    height: 1,
-                },
+            self.print_input_char(
+                frame,
+                input_char,
+                expected_input_char,
+                area.x + input_char_index as u16,
+                area.y,
             );
         }
     }
 
+    /// Print expected character at given position
+    /// using color indicating if the user typed it correctly.
+    fn print_input_char(
+        &self,
+        frame: &mut impl FrameWrapperInterface,
+        input_char: char,
+        expected_input_char: char,
+        x: u16,
+        y: u16,
+    ) {
+        let expected_input_char_str = match expected_input_char {
+            '\n' => NEWLINE_SYMBOL.to_string(),
+            _ => expected_input_char.to_string(),
+        };
+        let input: Paragraph<'_> = Paragraph::new(expected_input_char_str).style(
+            match input_char == expected_input_char {
+                true => Style::default()
+                    .bg(self.config.colors.correct_match_bg)
+                    .fg(self.config.colors.correct_match_fg),
+                false => Style::default()
+                    .bg(self.config.colors.incorrect_match_bg)
+                    .fg(self.config.colors.incorrect_match_fg),
+            },
+        );
+        frame.render_widget(
+            input,
+            Rect {
+                x,
+                y,
+                width: 1,
+                height: 1,
+            },
+        );
+    }
+
     /// Used for rendering text within given area and adjusted with given color.
     fn print_block_of_text(
         &self,
@@ -430,10 +1185,15 @@ impl Runner {
     /// Calculate the statistics of the test and return them.
     ///
     /// WPM is number of valid characters that are in the input after the test has finished
-    /// divided by 5, to get the "number of words typed", and divided by the duration of the test
-    /// normalized to 60 seconds.
+    /// divided by 5, to get the "number of words typed", and divided by the `active_time` of the
+    /// test normalized to 60 seconds.
     /// This way WPM is only counted in valid characters, so each mistake that wasn't corrected is
     /// not taken into consideration when calculating it.
+    /// `raw_wpm` is counted the same way from `raw_typed_characters_count`, and `cpm` is number of
+    /// valid characters per minute.
+    ///
+    /// `active_time` is the time from the first keystroke to the end of the test, without pauses,
+    /// see `TestClock`. `paused_time` and `pauses_count` describe pauses taken during the test.
     ///
     /// `raw_valid_characters_count` is number of times when valid character was pressed.
     /// `raw_mistakes_count is number` of times when invalid character was pressed.
@@ -448,10 +1208,21 @@ impl Runner {
     /// `typed_characters_count` is number of characters in the input after the test has finished.
     /// `accuracy` is ratio of `valid_characters_count` to `typed_characters_count`.
     ///
-    fn get_stats(&self) -> Stats {
+    /// `keystroke_interval` is the average time in milliseconds between two key presses in
+    /// `Editing` mode, and `keystroke_interval_deviation` is its standard deviation, together they
+    /// describe the rhythm of typing.
+    ///
+    /// `lines` are valid characters and mistakes after corrections counted separately for each
+    /// line of expected input, only when line breaks of the expected input have to be typed.
+    ///
+    /// `keys_stats` are hits, misses and time to press each character of expected input.
+    ///
+    /// `events` is the keystroke event log of the test.
+    ///
+    fn get_stats(&mut self, active_time: Duration) -> Stats {
         let typed_characters = self.input.chars();
         let typed_characters_count = typed_characters.clone().count();
-        let expected_input_str = self.expected_input.get_string(typed_characters_count);
+        let expected_input_str = self.expected_input.get_range(0, typed_characters_count);
         let expected_characters = expected_input_str.chars();
 
         let mistakes_count = typed_characters
@@ -461,16 +1232,56 @@ impl Runner {
             .count() as u64;
         let valid_characters_count = typed_characters_count as u64 - mistakes_count;
 
-        fn get_percentage(numerator: f64, denominator: f64) -> f64 {
-            if denominator == 0.0 {
-                return 0.0;
-            }
+        let mut lines: Vec<LineStats> = vec![];
+        if self.has_line_breaks() {
+            let mut line_stats = LineStats::default();
+            for (input_char, expected_input_char) in
+                typed_characters.clone().zip(expected_characters)
+            {
+                if input_char == expected_input_char {
+                    line_stats.valid_characters_count += 1;
+                } else {
+                    line_stats.mistakes_count += 1;
+                }
 
-            numerator / denominator * 100.0
+                if expected_input_char == '\n' {
+                    lines.push(line_stats);
+                    line_stats = LineStats::default();
+                }
+            }
+            if line_stats.valid_characters_count + line_stats.mistakes_count > 0 {
+                lines.push(line_stats);
+            }
         }
 
+        let intervals_ms = self
+            .keystroke_intervals
+            .iter()
+            .map(|interval| interval.as_secs_f64() * 1000.0)
+            .collect::<Vec<_>>();
+        let keystroke_interval = match intervals_ms.is_empty() {
+            true => 0.0,
+            false => intervals_ms.iter().sum::<f64>() / intervals_ms.len() as f64,
+        };
+        let keystroke_interval_deviation = match intervals_ms.is_empty() {
+            true => 0.0,
+            false => (intervals_ms
+                .iter()
+                .map(|interval| (interval - keystroke_interval).powi(2))
+                .sum::<f64>()
+                / intervals_ms.len() as f64)
+                .sqrt(),
+        };
+
+        let raw_typed_characters_count = self.raw_valid_characters_count + self.raw_mistakes_count;
+
         Stats {
-            wpm: valid_characters_count as f64 / 5.0 * 60.0 / self.config.duration.as_secs() as f64,
+            wpm: get_wpm(valid_characters_count, active_time),
+            raw_wpm: get_wpm(raw_typed_characters_count, active_time),
+            cpm: get_wpm(valid_characters_count, active_time) * 5.0,
+            active_time,
+            paused_time: self.clock.get_paused_time(Instant::now()),
+            pauses_count: self.clock.get_pauses_count(),
 
             raw_accuracy: get_percentage(
                 self.raw_valid_characters_count as f64,
@@ -478,7 +1289,7 @@ impl Runner {
             ),
             raw_valid_characters_count: self.raw_valid_characters_count,
             raw_mistakes_count: self.raw_mistakes_count,
-            raw_typed_characters_count: self.raw_valid_characters_count + self.raw_mistakes_count,
+            raw_typed_characters_count,
 
             accuracy: get_percentage(
                 (typed_characters_count - mistakes_count as usize) as f64,
@@ -487,10 +1298,35 @@ impl Runner {
             valid_characters_count,
             mistakes_count,
             typed_characters_count: typed_characters_count as u64,
+
+            keystroke_interval,
+            keystroke_interval_deviation,
+
+            lines,
+
+            keys_stats: self.keys_stats.clone(),
+            events: self.events.clone(),
         }
     }
 }
 
+/// Calculates words per minute, a word being 5 characters
+fn get_wpm(characters_count: u64, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.0;
+    }
+
+    characters_count as f64 / 5.0 * 60.0 / elapsed.as_secs_f64()
+}
+
+fn get_percentage(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        return 0.0;
+    }
+
+    numerator / denominator * 100.0
+}
+
 /// Used for generating mocks using `mockall` crate
 #[automock]
 pub trait FrameWrapperInterface {
@@ -528,8 +1364,12 @@ impl<'a, 'b> FrameWrapperInterface for FrameWrapper<'a, 'b> {
 mod test {
     use mockall::predicate;
 
+    use crate::code_input::ExpectedCode;
     use crate::expected_input::{ExpectedInput, MockExpectedInputInterface};
+    use crate::weak_keys::WeakKeys;
+    use crate::zen_input::ExpectedNothing;
     use ratatui::{backend::TestBackend, buffer::Buffer};
+    use std::collections::HashMap;
     use std::io::Write;
 
     use super::*;
@@ -585,41 +1425,41 @@ mod test {
     #[test]
     fn should_render_single_line_input() {
         let config = Config::default();
-        let time_left = config.duration;
 
         let mut expected_input = MockExpectedInputInterface::default();
 
         expected_input
-            .expect_get_string()
-            .with(predicate::eq(2 * 50))
-            .return_const("foobarbaaz".repeat(10));
+            .expect_get_range()
+            .returning(|start, end| "foobarbaaz".repeat(20).get(start..end).unwrap().to_string());
 
-        let mut runner = Runner::new(config, expected_input);
+        let mut runner = Runner::new(config, Box::new(expected_input));
 
         runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
         runner.input = "foo".to_string();
 
         let mut frame = MockFrameWrapperInterface::default();
 
-        frame.expect_area().times(2).return_const(Rect {
+        frame.expect_area().times(1).return_const(Rect {
             x: 39,
             y: 1,
             width: 50,
-            height: 1,
+            height: 4,
         });
 
+        // 2 info messages, 3 typed characters and 3 lines of expected input
         frame
             .expect_render_widget::<Paragraph>()
-            .times(7)
+            .times(8)
             .return_const(());
 
         frame
             .expect_set_cursor()
-            .with(predicate::eq(42), predicate::eq(1))
+            .with(predicate::eq(42), predicate::eq(2))
             .times(1)
             .return_const(());
 
-        runner.render(&mut frame, time_left.as_secs());
+        runner.render(&mut frame, Duration::from_secs(0));
     }
 
     #[test]
@@ -628,8 +1468,9 @@ mod test {
         config.duration = Duration::from_secs(30);
         let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
 
-        let mut runner = Runner::new(config, expected_input);
+        let mut runner = Runner::new(config, Box::new(expected_input));
         runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
         runner.input = "foobar".to_string();
 
         let buffer = create_buffer(
@@ -647,26 +1488,517 @@ mod test {
                 ],
                 vec![
                     ("foobar", Color::Green),
-                    ("bazquxaboba foobarbazquxaboba foobarbazquxab", Color::Gray),
+                    ("bazquxaboba foobarbazquxaboba ", Color::Gray),
+                ],
+                vec![("foobarbazquxaboba foobarbazquxaboba ", Color::DarkGray)],
+            ],
+        );
+
+        test_runner(&mut runner, buffer, |frame, runner| {
+            runner.render(frame, Duration::from_secs(0));
+        });
+    }
+
+    #[test]
+    fn should_scroll_wrapped_lines_centred_in_max_width() {
+        let (mut config, _config_file) = get_config(vec!["foo", "bar", "baz"]);
+        config.max_width = 20;
+        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+
+        let mut runner = Runner::new(config, Box::new(expected_input));
+        runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
+        runner.input = "foo bar baz ".repeat(4)[..43].to_string();
+
+        let buffer = create_buffer(
+            Rect {
+                x: 0,
+                y: 0,
+                width: 50,
+                height: 4,
+            },
+            vec![
+                vec![
+                    ("30 seconds left", Color::Yellow),
+                    ("    ", Color::Reset),
+                    ("press '<Esc>' to pause the test", Color::Yellow),
+                ],
+                vec![
+                    (&" ".repeat(15), Color::Reset),
+                    ("baz foo bar baz foo ", Color::Green),
+                ],
+                vec![
+                    (&" ".repeat(15), Color::Reset),
+                    ("bar", Color::Green),
+                    (" baz foo bar baz ", Color::Gray),
+                ],
+                vec![
+                    (&" ".repeat(15), Color::Reset),
+                    ("foo bar baz foo bar ", Color::DarkGray),
+                ],
+            ],
+        );
+
+        test_runner(&mut runner, buffer, |frame, runner| {
+            runner.render(frame, Duration::from_secs(0));
+        });
+    }
+
+    #[test]
+    fn should_render_live_stats() {
+        let (mut config, _config_file) = get_config(vec!["foo"]);
+        config.live_stats = vec![
+            LiveStat::Wpm,
+            LiveStat::RawWpm,
+            LiveStat::Accuracy,
+            LiveStat::Errors,
+        ];
+        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+
+        let mut runner = Runner::new(config, Box::new(expected_input));
+        runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
+        runner.push_char('x');
+        runner.remove_last_word();
+        for c in "foo".chars() {
+            runner.push_char(c);
+        }
+
+        let buffer = create_buffer(
+            Rect {
+                x: 0,
+                y: 0,
+                width: 50,
+                height: 3,
+            },
+            vec![
+                vec![
+                    ("24 seconds left", Color::Yellow),
+                    ("    ", Color::Reset),
+                    ("press '<Esc>' to pause the test", Color::Yellow),
+                ],
+                vec![("6 wpm  8 raw wpm  75% accuracy  1 error", Color::Yellow)],
+                vec![
+                    ("foo", Color::Green),
+                    (" foo foo foo foo foo foo foo foo foo foo foo ", Color::Gray),
+                ],
+            ],
+        );
+
+        test_runner(&mut runner, buffer, |frame, runner| {
+            runner.render(frame, Duration::from_secs(6));
+        });
+    }
+
+    #[test]
+    fn should_render_only_input_in_focus_mode() {
+        let (mut config, _config_file) = get_config(vec!["foo"]);
+        config.focus = true;
+        config.live_stats = vec![LiveStat::Wpm];
+        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+
+        let mut runner = Runner::new(config, Box::new(expected_input));
+        runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
+        runner.input = "fo".to_string();
+
+        let buffer = create_buffer(
+            Rect {
+                x: 0,
+                y: 0,
+                width: 20,
+                height: 1,
+            },
+            vec![vec![
+                ("fo", Color::Green),
+                ("o foo foo foo foo ", Color::Gray),
+            ]],
+        );
+
+        test_runner(&mut runner, buffer, |frame, runner| {
+            runner.render(frame, Duration::from_secs(6));
+        });
+    }
+
+    #[test]
+    fn should_render_words_progress_in_words_mode() {
+        let (mut config, _config_file) = get_config(vec!["foo", "bar", "baz"]);
+        config.mode = TestMode::Words;
+        config.words = 3;
+        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+
+        let mut runner = Runner::new(config, Box::new(expected_input));
+        runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
+        runner.input = "foo bar b".to_string();
+
+        let buffer = create_buffer(
+            Rect {
+                x: 0,
+                y: 0,
+                width: 50,
+                height: 2,
+            },
+            vec![
+                vec![
+                    ("2/3", Color::Yellow),
+                    ("                ", Color::Reset),
+                    ("press '<Esc>' to pause the test", Color::Yellow),
+                ],
+                vec![
+                    ("foo bar b", Color::Green),
+                    ("az foo bar baz foo bar baz foo bar baz ", Color::Gray),
+                ],
+            ],
+        );
+
+        test_runner(&mut runner, buffer, |frame, runner| {
+            runner.render(frame, Duration::from_secs(0));
+        });
+    }
+
+    #[test]
+    fn should_find_end_of_words_across_chunks_of_expected_input() {
+        let config = Config {
+            mode: TestMode::Words,
+            words: 2,
+            ..Config::default()
+        };
+        // the second word ends exactly between the first and the second chunk
+        let text = format!("{} {} baz ", "a".repeat(10), "b".repeat(9));
+        let mut expected_input = MockExpectedInputInterface::default();
+        expected_input
+            .expect_get_range()
+            .returning(move |start, end| {
+                text.chars()
+                    .skip(start)
+                    .take(end.saturating_sub(start))
+                    .collect()
+            });
+
+        let mut runner = Runner::new(config, Box::new(expected_input));
+
+        assert_eq!(runner.get_words_mode_input_len(), 20);
+        runner.config.words = 3;
+        assert_eq!(runner.get_words_mode_input_len(), 24);
+        // expected input ends before the last word
+        runner.config.words = 5;
+        assert_eq!(runner.get_words_mode_input_len(), 25);
+    }
+
+    #[test]
+    fn should_wait_for_first_character_with_type_to_start() {
+        let (mut config, _config_file) = get_config(vec!["foo"]);
+        config.type_to_start = true;
+        config.max_width = 20;
+        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+
+        let mut runner = Runner::new(config, Box::new(expected_input));
+        assert!(matches!(runner.input_mode, InputMode::Editing));
+
+        let buffer = create_buffer(
+            Rect {
+                x: 0,
+                y: 0,
+                width: 80,
+                height: 2,
+            },
+            vec![
+                vec![
+                    ("30 seconds left", Color::Yellow),
+                    ("  ", Color::Reset),
+                    (
+                        "start typing to begin the test, press '<Esc>' for other options",
+                        Color::Yellow,
+                    ),
+                ],
+                vec![
+                    ("                              ", Color::Reset),
+                    ("foo foo foo foo foo ", Color::Gray),
                 ],
-                vec![(
-                    "oba foobarbazquxaboba foobarbazquxaboba foobarbazq",
-                    Color::DarkGray,
-                )],
             ],
         );
 
         test_runner(&mut runner, buffer, |frame, runner| {
-            let duration = 30;
-            runner.render(frame, duration);
+            runner.render(frame, Duration::from_secs(0));
         });
     }
 
+    #[test]
+    fn should_give_back_expected_input_to_repeat_adaptive_test() {
+        let config = Config {
+            adaptive: true,
+            seed: Some(42),
+            ..Config::default()
+        };
+        let expected_input = ExpectedInput::new_with_weak_keys(&config, &WeakKeys::default())
+            .expect("unable to create expected input");
+        let mut runner = Runner::new(config.clone(), Box::new(expected_input));
+        let text = runner.expected_input.get_range(0, 100);
+
+        // results of the finished test change weak keys, so a new expected input differs
+        let weak_keys = WeakKeys {
+            letters: HashMap::from([('z', 100.0)]),
+            ..WeakKeys::default()
+        };
+        let mut new_expected_input = ExpectedInput::new_with_weak_keys(&config, &weak_keys)
+            .expect("unable to create expected input");
+        assert_ne!(new_expected_input.get_range(0, 100), text);
+
+        let mut repeated_expected_input = runner.into_expected_input();
+        assert_eq!(repeated_expected_input.get_range(0, 100), text);
+    }
+
+    #[test]
+    fn should_restart_test_with_changed_setting() {
+        let mut runner = Runner::new(Config::default(), Box::new(ExpectedNothing))
+            .with_settings_window(Some(SettingsWindow::new()));
+        let mut expected_config = Config::default();
+        SettingsWindow::new().change_selected(&mut expected_config, true);
+
+        match runner.change_setting(true) {
+            TestOutcome::Restart {
+                config,
+                settings_window,
+            } => {
+                assert_eq!(config.mode, expected_config.mode);
+                assert_ne!(config.mode, Config::default().mode);
+                assert!(settings_window.is_some());
+            }
+            TestOutcome::Finished(_) => panic!("test should be restarted"),
+        }
+    }
+
+    #[test]
+    fn should_reopen_settings_window_in_restarted_test() {
+        let config = Config {
+            type_to_start: true,
+            ..Config::default()
+        };
+        let runner = Runner::new(config.clone(), Box::new(ExpectedNothing));
+        assert!(matches!(
+            runner.get_restart_outcome(),
+            TestOutcome::Restart {
+                settings_window: None,
+                ..
+            }
+        ));
+
+        let runner = Runner::new(config, Box::new(ExpectedNothing))
+            .with_settings_window(Some(SettingsWindow::new()));
+        assert!(matches!(runner.input_mode, InputMode::Normal));
+        assert!(matches!(
+            runner.get_restart_outcome(),
+            TestOutcome::Restart {
+                settings_window: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn should_remove_last_word_keeping_line_breaks() {
+        let config = Config::default();
+        let expected_input = MockExpectedInputInterface::default();
+        let mut runner = Runner::new(config, Box::new(expected_input));
+
+        runner.input = "foo bar\nbaz qux ".to_string();
+        runner.remove_last_word();
+        assert_eq!(runner.input, "foo bar\nbaz ");
+
+        runner.remove_last_word();
+        assert_eq!(runner.input, "foo bar\n");
+
+        runner.remove_last_word();
+        runner.remove_last_word();
+        assert_eq!(runner.input, "");
+    }
+
+    fn get_code_runner(code: &str) -> (Runner, tempfile::NamedTempFile) {
+        let mut code_file = tempfile::NamedTempFile::new().expect("Unable to create temp file");
+        code_file
+            .write_all(code.as_bytes())
+            .expect("Unable to write to temp file");
+        let config = Config {
+            mode: TestMode::Code,
+            code_path: Some(code_file.path().to_path_buf()),
+            ..Config::default()
+        };
+        let expected_input = ExpectedCode::new(&config).expect("unable to create expected code");
+
+        (Runner::new(config, Box::new(expected_input)), code_file)
+    }
+
+    #[test]
+    fn should_render_code_preserving_lines() {
+        let (mut runner, _code_file) = get_code_runner("fn a() {\n    b\n}");
+        runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
+        runner.input = "fn a() {".to_string();
+        runner.push_char('\n');
+        runner.skip_indentation();
+
+        assert_eq!(runner.input, "fn a() {\n    ");
+        assert_eq!(runner.raw_valid_characters_count, 1);
+
+        let buffer = create_buffer(
+            Rect {
+                x: 0,
+                y: 0,
+                width: 20,
+                height: 3,
+            },
+            vec![
+                vec![("fn a() {⏎", Color::Green)],
+                vec![("    ", Color::Green), ("b⏎", Color::Gray)],
+                vec![("}", Color::DarkGray)],
+            ],
+        );
+
+        test_runner(&mut runner, buffer, |frame, runner| {
+            let area = frame.area();
+            runner.render_expected_lines_area(frame, area);
+        });
+    }
+
+    #[test]
+    fn should_count_stats_per_line() {
+        let (mut runner, _code_file) = get_code_runner("ab\ncd\nef");
+        runner.input = "ab\nxd\ne".to_string();
+
+        let stats = runner.get_stats(Duration::from_secs(30));
+
+        assert_eq!(stats.lines.len(), 3);
+        assert_eq!(stats.lines[0].get_accuracy(), 100.0);
+        assert_eq!(stats.lines[1].mistakes_count, 1);
+        assert_eq!(stats.lines[1].valid_characters_count, 2);
+        assert_eq!(stats.lines[2].valid_characters_count, 1);
+    }
+
+    #[test]
+    fn should_count_speed_from_active_time() {
+        let (config, _config_file) = get_config(vec!["foo"]);
+        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+        let mut runner = Runner::new(config, Box::new(expected_input));
+        runner.input = "foo fox".to_string();
+        runner.raw_valid_characters_count = 8;
+        runner.raw_mistakes_count = 2;
+        let now = Instant::now();
+        runner.clock.start(now);
+        runner.clock.pause(now);
+
+        let stats = runner.get_stats(Duration::from_secs(6));
+
+        assert_eq!(stats.wpm, 12.0);
+        assert_eq!(stats.raw_wpm, 20.0);
+        assert_eq!(stats.cpm, 60.0);
+        assert_eq!(stats.active_time, Duration::from_secs(6));
+        assert_eq!(stats.pauses_count, 1);
+    }
+
+    #[test]
+    fn should_not_count_pauses_in_speed() {
+        let (config, _config_file) = get_config(vec!["foo"]);
+        let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
+        let mut runner = Runner::new(config, Box::new(expected_input));
+        runner.input = "foo fox".to_string();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        runner.clock.start(at(0));
+        runner.clock.record_keystroke(at(1));
+        runner.clock.pause(at(4));
+        runner.clock.resume(at(14));
+        runner.clock.record_keystroke(at(17));
+        let active_time = runner.clock.get_active_time(at(17), None);
+
+        let stats = runner.get_stats(active_time);
+
+        assert_eq!(stats.active_time, Duration::from_secs(6));
+        assert_eq!(stats.wpm, 12.0);
+        assert_eq!(stats.paused_time, Duration::from_secs(10));
+        assert_eq!(stats.pauses_count, 1);
+    }
+
+    #[test]
+    fn should_render_zen_input_in_lines() {
+        let config = Config {
+            mode: TestMode::Zen,
+            ..Config::default()
+        };
+        let mut runner = Runner::new(config, Box::new(ExpectedNothing));
+        runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
+        for c in "foo bar".chars() {
+            runner.push_char(c);
+        }
+
+        assert_eq!(runner.raw_valid_characters_count, 7);
+        assert_eq!(runner.raw_mistakes_count, 0);
+
+        let buffer = create_buffer(
+            Rect {
+                x: 0,
+                y: 0,
+                width: 4,
+                height: 2,
+            },
+            vec![vec![("foo ", Color::Green)], vec![("bar", Color::Green)]],
+        );
+
+        test_runner(&mut runner, buffer, |frame, runner| {
+            runner.render_zen_input_area(frame, frame.area());
+        });
+    }
+
+    #[test]
+    fn should_not_render_zen_input_in_empty_area() {
+        let mut runner = Runner::new(Config::default(), Box::new(ExpectedNothing));
+        runner.input = "foo".to_string();
+        let mut frame = MockFrameWrapperInterface::default();
+        frame
+            .expect_render_widget::<Paragraph>()
+            .times(0)
+            .return_const(());
+
+        let cursor_position = runner.render_zen_input_area(
+            &mut frame,
+            Rect {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 3,
+            },
+        );
+
+        assert_eq!(cursor_position, (0, 0));
+    }
+
+    #[test]
+    fn should_count_keystroke_rhythm() {
+        let config = Config {
+            mode: TestMode::Zen,
+            ..Config::default()
+        };
+        let mut runner = Runner::new(config, Box::new(ExpectedNothing));
+        runner.input = "foo".to_string();
+        runner.keystroke_intervals = vec![
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(300),
+        ];
+
+        let stats = runner.get_stats(Duration::from_secs(60));
+
+        assert_eq!(stats.keystroke_interval, 200.0);
+        assert!((stats.keystroke_interval_deviation - 81.65).abs() < 0.01);
+    }
+
     #[test]
     fn should_print_input() {
         let (config, _config_file) = get_config(vec!["foo"]);
         let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
-        let mut runner = Runner::new(config, expected_input);
+        let mut runner = Runner::new(config, Box::new(expected_input));
 
         runner.input = "foo".to_string();
 
@@ -684,13 +2016,13 @@ mod test {
             runner.print_input(
                 frame,
                 "foo",
+                0,
                 Rect {
                     x: 0,
                     y: 0,
                     width: 50,
                     height: 1,
                 },
-                50,
             );
         });
     }
@@ -699,7 +2031,7 @@ mod test {
     fn should_print_block_of_text() {
         let (config, _config_file) = get_config(vec!["foo"]);
         let expected_input = ExpectedInput::new(&config).expect("unable to create expected input");
-        let mut runner = Runner::new(config, expected_input);
+        let mut runner = Runner::new(config, Box::new(expected_input));
 
         let buffer = create_buffer(
             Rect {
@@ -732,7 +2064,7 @@ mod test {
     fn should_not_move_cursor_in_normal_mode() {
         let config = Config::default();
         let expected_input = MockExpectedInputInterface::default();
-        let runner = Runner::new(config, expected_input);
+        let runner = Runner::new(config, Box::new(expected_input));
 
         let mut frame = MockFrameWrapperInterface::default();
 
@@ -755,9 +2087,10 @@ mod test {
         let config = Config::default();
 
         let expected_input = MockExpectedInputInterface::default();
-        let mut runner = Runner::new(config, expected_input);
+        let mut runner = Runner::new(config, Box::new(expected_input));
 
         runner.input_mode = InputMode::Editing;
+        runner.clock.start(Instant::now());
 
         let mut frame = MockFrameWrapperInterface::default();
 
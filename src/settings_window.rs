@@ -0,0 +1,360 @@
+//! Module with the settings window rendered on top of the test in `Normal` mode.
+//!
+//! It lists options deciding about the content of the test, so they can be changed
+//! without restarting the program or editing the config file.
+//! Options are selected and changed with keys of the `select_previous`, `select_next`,
+//! `previous_value` and `next_value` actions, arrow keys by default. Every change starts
+//! the test again with a new text generated from changed options, and the window stays open
+//! on top of it until it's closed.
+//! Changed options can also be saved to the config file.
+
+use clap::ValueEnum;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::dictionary::Language;
+use crate::keybindings::Action;
+use crate::runner::FrameWrapperInterface;
+use crate::test_mode::TestMode;
+
+/// Step by which `duration` is changed, also its minimal value
+const DURATION_STEP: Duration = Duration::from_secs(15);
+
+/// Step by which `words` is changed, also its minimal value
+const WORDS_STEP: usize = 10;
+
+/// Step by which ratios are changed, also their minimal value
+const RATIO_STEP: f64 = 0.05;
+
+/// Options that can be changed in the settings window
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Setting {
+    Mode,
+    Duration,
+    Words,
+    Language,
+    Numbers,
+    NumbersRatio,
+    Symbols,
+    SymbolsRatio,
+    Uppercase,
+    UppercaseRatio,
+}
+
+const SETTINGS: [Setting; 10] = [
+    Setting::Mode,
+    Setting::Duration,
+    Setting::Words,
+    Setting::Language,
+    Setting::Numbers,
+    Setting::NumbersRatio,
+    Setting::Symbols,
+    Setting::SymbolsRatio,
+    Setting::Uppercase,
+    Setting::UppercaseRatio,
+];
+
+impl Setting {
+    fn get_name(&self) -> &'static str {
+        match self {
+            Setting::Mode => "mode",
+            Setting::Duration => "duration",
+            Setting::Words => "words",
+            Setting::Language => "dictionary",
+            Setting::Numbers => "numbers",
+            Setting::NumbersRatio => "numbers ratio",
+            Setting::Symbols => "symbols",
+            Setting::SymbolsRatio => "symbols ratio",
+            Setting::Uppercase => "uppercase",
+            Setting::UppercaseRatio => "uppercase ratio",
+        }
+    }
+
+    fn get_value(&self, config: &Config) -> String {
+        match self {
+            Setting::Mode => format!("{:?}", config.mode).to_lowercase(),
+            Setting::Duration => format!("{}s", config.duration.as_secs()),
+            Setting::Words => config.words.to_string(),
+            Setting::Language => match &config.dictionary_path {
+                Some(dictionary_path) => dictionary_path.display().to_string(),
+                None => config.language.get_name().to_string(),
+            },
+            Setting::Numbers => config.numbers.to_string(),
+            Setting::NumbersRatio => format!("{:.2}", config.numbers_ratio),
+            Setting::Symbols => config.symbols.to_string(),
+            Setting::SymbolsRatio => format!("{:.2}", config.symbols_ratio),
+            Setting::Uppercase => config.uppercase.to_string(),
+            Setting::UppercaseRatio => format!("{:.2}", config.uppercase_ratio),
+        }
+    }
+
+    /// Sets the next value of the option, or the previous one if `forward` is false
+    fn change(&self, config: &mut Config, forward: bool) {
+        match self {
+            Setting::Mode => {
+                // `text` mode can't be chosen if there is no text to type
+                let modes = TestMode::value_variants()
+                    .iter()
+                    .filter(|mode| **mode != TestMode::Text || config.text_path.is_some())
+                    .copied()
+                    .collect::<Vec<_>>();
+                config.mode = get_adjacent(&modes, config.mode, forward);
+            }
+            Setting::Duration => {
+                config.duration = match forward {
+                    true => config.duration + DURATION_STEP,
+                    false => config
+                        .duration
+                        .saturating_sub(DURATION_STEP)
+                        .max(DURATION_STEP),
+                }
+            }
+            Setting::Words => {
+                config.words = match forward {
+                    true => config.words + WORDS_STEP,
+                    false => config.words.saturating_sub(WORDS_STEP).max(WORDS_STEP),
+                }
+            }
+            Setting::Language => {
+                // dictionary file is replaced with a builtin dictionary
+                config.language = match config.dictionary_path.take() {
+                    Some(_) => config.language,
+                    None => get_adjacent(Language::value_variants(), config.language, forward),
+                };
+            }
+            Setting::Numbers => config.numbers = !config.numbers,
+            Setting::NumbersRatio => {
+                config.numbers_ratio = change_ratio(config.numbers_ratio, forward)
+            }
+            Setting::Symbols => config.symbols = !config.symbols,
+            Setting::SymbolsRatio => {
+                config.symbols_ratio = change_ratio(config.symbols_ratio, forward)
+            }
+            Setting::Uppercase => config.uppercase = !config.uppercase,
+            Setting::UppercaseRatio => {
+                config.uppercase_ratio = change_ratio(config.uppercase_ratio, forward)
+            }
+        }
+    }
+}
+
+/// Returns the value following `value` in `values`, or preceding it if `forward` is false,
+/// wrapping around at both ends
+fn get_adjacent<T: Copy + PartialEq>(values: &[T], value: T, forward: bool) -> T {
+    let index = values.iter().position(|v| *v == value).unwrap_or(0);
+    let adjacent_index = match forward {
+        true => (index + 1) % values.len(),
+        false => (index + values.len() - 1) % values.len(),
+    };
+
+    values[adjacent_index]
+}
+
+/// Changes the ratio by `RATIO_STEP`, keeping it between `RATIO_STEP` and `1.0`
+fn change_ratio(ratio: f64, forward: bool) -> f64 {
+    let ratio = match forward {
+        true => ratio + RATIO_STEP,
+        false => ratio - RATIO_STEP,
+    };
+
+    ((ratio / RATIO_STEP).round() * RATIO_STEP).clamp(RATIO_STEP, 1.0)
+}
+
+#[derive(Debug, Clone)]
+pub struct SettingsWindow {
+    selected_index: usize,
+}
+
+impl SettingsWindow {
+    pub fn new() -> Self {
+        SettingsWindow { selected_index: 0 }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected_index = (self.selected_index + SETTINGS.len() - 1) % SETTINGS.len();
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected_index = (self.selected_index + 1) % SETTINGS.len();
+    }
+
+    /// Sets the next value of the selected option, or the previous one if `forward` is false
+    pub fn change_selected(&self, config: &mut Config, forward: bool) {
+        SETTINGS[self.selected_index].change(config, forward);
+    }
+
+    pub fn render(&self, frame: &mut impl FrameWrapperInterface, config: &Config) {
+        let mut lines = vec![String::new()];
+        for (index, setting) in SETTINGS.iter().enumerate() {
+            let marker = match index == self.selected_index {
+                true => '>',
+                false => ' ',
+            };
+            lines.push(format!(
+                " {} {:<16} {}",
+                marker,
+                setting.get_name(),
+                setting.get_value(config)
+            ));
+        }
+        let keybindings = &config.keybindings;
+        let navigation = [
+            (
+                format!(
+                    "{}/{}",
+                    keybindings.describe_all(Action::SelectPrevious),
+                    keybindings.describe_all(Action::SelectNext)
+                ),
+                "Select an option",
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    keybindings.describe_all(Action::PreviousValue),
+                    keybindings.describe_all(Action::NextValue)
+                ),
+                "Change the option",
+            ),
+            (
+                keybindings.describe_all(Action::SaveSettings),
+                "Save options to the config file",
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    keybindings.describe_all(Action::Close),
+                    keybindings.describe_all(Action::Settings)
+                ),
+                "Close the window ",
+            ),
+        ];
+        let keys_width = navigation
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        lines.push(String::new());
+        lines.extend(
+            navigation
+                .iter()
+                .map(|(keys, description)| format!(" {:<keys_width$} - {}", keys, description)),
+        );
+        lines.push(String::new());
+
+        let frame_rect = frame.area();
+        let window_width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0) as u16;
+        let window_height = lines.len() as u16;
+
+        if frame_rect.height <= window_height + 1 || frame_rect.width <= window_width + 2 {
+            let paragraph = Paragraph::new(format!(
+                "Terminal window is too small\nto display the settings window\nresize the terminal\nor press '{}'\nto return to the test",
+                keybindings.describe(Action::Close)
+            ))
+            .style(Style::default().fg(Color::Red).bg(Color::Black));
+
+            frame.render_widget(Clear, frame_rect);
+            frame.render_widget(paragraph, frame_rect);
+
+            return;
+        }
+
+        let area = Rect::new(
+            frame_rect.x + (frame_rect.width - window_width - 2) / 2,
+            frame_rect.y + (frame_rect.height - window_height - 2) / 2,
+            window_width + 2,
+            window_height + 2,
+        );
+        let block = Block::default().title(" Settings ").borders(Borders::ALL);
+        let inner_area = block.inner(area);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        for (index, line) in lines.into_iter().enumerate() {
+            let style = match index == self.selected_index + 1 {
+                true => Style::default().fg(Color::Yellow),
+                false => Style::default(),
+            };
+            frame.render_widget(
+                Paragraph::new(line).style(style),
+                Rect {
+                    y: inner_area.y + index as u16,
+                    height: 1,
+                    ..inner_area
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybindings::parse_keys;
+    use crate::runner::FrameWrapper;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn should_change_selected_option() {
+        let mut settings_window = SettingsWindow::new();
+        let mut config = Config::default();
+
+        settings_window.change_selected(&mut config, true);
+        assert_eq!(config.mode, TestMode::Words);
+        // there is no text to type in `text` mode
+        settings_window.change_selected(&mut config, true);
+        settings_window.change_selected(&mut config, true);
+        assert_eq!(config.mode, TestMode::Code);
+
+        settings_window.select_previous();
+        settings_window.change_selected(&mut config, false);
+        assert!((config.uppercase_ratio - 0.1).abs() < f64::EPSILON);
+        for _ in 0..30 {
+            settings_window.change_selected(&mut config, true);
+        }
+        assert_eq!(config.uppercase_ratio, 1.0);
+
+        settings_window.select_next();
+        settings_window.select_next();
+        settings_window.change_selected(&mut config, false);
+        assert_eq!(config.duration, Duration::from_secs(15));
+        settings_window.change_selected(&mut config, false);
+        assert_eq!(config.duration, Duration::from_secs(15));
+    }
+
+    #[test]
+    fn should_describe_configured_keys() {
+        let mut config = Config::default();
+        config.keybindings.settings =
+            parse_keys(&["f2".to_string()]).expect("Unable to parse keys");
+        config.keybindings.save_settings =
+            parse_keys(&["ctrl+s".to_string()]).expect("Unable to parse keys");
+        let settings_window = SettingsWindow::new();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal
+            .draw(|frame| settings_window.render(&mut FrameWrapper::new(frame), &config))
+            .unwrap();
+
+        let text = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert!(text.contains("'<Ctrl+s>'"));
+        assert!(text.contains("'<Esc>'/'<F2>'"));
+        assert!(!text.contains("'o'"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Module with the clock measuring time of the test.
+//!
+//! Time of the test is the time since the test was started without the time spent on pauses.
+//! The clock also remembers when the first and the last keystroke happened, so statistics
+//! can be counted from the time the user was actually typing: from the first keystroke
+//! to the last one, or to the time limit in `time` and `text` modes.
+//!
+//! Every method takes the current instant, so the runner reads the system clock once
+//! per event and the clock can be tested without waiting.
+
+use std::time::{Duration, Instant};
+
+/// Clock of a single test, started once and paused and resumed any number of times
+#[derive(Debug, Default)]
+pub struct TestClock {
+    start_time: Option<Instant>,
+    pause_time: Option<Instant>,
+    paused_time: Duration,
+    pauses_count: u64,
+    first_keystroke_time: Option<Duration>,
+    last_keystroke_time: Option<Duration>,
+}
+
+impl TestClock {
+    /// Starts the clock, does nothing if it's already started
+    pub fn start(&mut self, now: Instant) {
+        if self.start_time.is_none() {
+            self.start_time = Some(now);
+        }
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.start_time.is_some()
+    }
+
+    /// Stops the clock until it's resumed, does nothing if it isn't running
+    pub fn pause(&mut self, now: Instant) {
+        if self.is_started() && self.pause_time.is_none() {
+            self.pause_time = Some(now);
+            self.pauses_count += 1;
+        }
+    }
+
+    /// Runs the clock again after a pause, adding the pause to the paused time
+    pub fn resume(&mut self, now: Instant) {
+        if let Some(pause_time) = self.pause_time.take() {
+            self.paused_time += now.saturating_duration_since(pause_time);
+        }
+    }
+
+    /// Time since the start of the test without pauses, stopped while the clock is paused
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        match self.start_time {
+            None => Duration::from_secs(0),
+            Some(start_time) => self
+                .pause_time
+                .unwrap_or(now)
+                .saturating_duration_since(start_time)
+                .saturating_sub(self.paused_time),
+        }
+    }
+
+    /// Remembers the time of a keystroke, the first one is remembered separately
+    pub fn record_keystroke(&mut self, now: Instant) {
+        let time = self.elapsed(now);
+        self.first_keystroke_time.get_or_insert(time);
+        self.last_keystroke_time = Some(time);
+    }
+
+    /// Time the user was typing, from the first keystroke to the end of the test
+    ///
+    /// Test with a `time_limit` ends when the limit is reached, or now if it ended earlier,
+    /// and test without it ends with the last keystroke.
+    pub fn get_active_time(&self, now: Instant, time_limit: Option<Duration>) -> Duration {
+        let end_time = match time_limit {
+            Some(time_limit) => self.elapsed(now).min(time_limit),
+            None => self
+                .last_keystroke_time
+                .unwrap_or_else(|| self.elapsed(now)),
+        };
+
+        end_time.saturating_sub(self.first_keystroke_time.unwrap_or_default())
+    }
+
+    /// Total time of pauses, including the current one
+    pub fn get_paused_time(&self, now: Instant) -> Duration {
+        let current_pause = self
+            .pause_time
+            .map(|pause_time| now.saturating_duration_since(pause_time))
+            .unwrap_or_default();
+
+        self.paused_time + current_pause
+    }
+
+    pub fn get_pauses_count(&self) -> u64 {
+        self.pauses_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_count_pauses_in_elapsed_time() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut clock = TestClock::default();
+
+        clock.pause(at(0));
+        assert_eq!(clock.elapsed(at(1)), Duration::from_secs(0));
+        assert_eq!(clock.get_pauses_count(), 0);
+
+        clock.start(at(1));
+        clock.pause(at(4));
+        assert_eq!(clock.elapsed(at(10)), Duration::from_secs(3));
+        assert_eq!(clock.get_paused_time(at(10)), Duration::from_secs(6));
+
+        clock.resume(at(10));
+        clock.start(at(11));
+        assert_eq!(clock.elapsed(at(12)), Duration::from_secs(5));
+        assert_eq!(clock.get_paused_time(at(12)), Duration::from_secs(6));
+        assert_eq!(clock.get_pauses_count(), 1);
+    }
+
+    #[test]
+    fn should_count_active_time_from_first_keystroke() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut clock = TestClock::default();
+
+        clock.start(at(0));
+        clock.record_keystroke(at(2));
+        clock.pause(at(3));
+        clock.resume(at(8));
+        clock.record_keystroke(at(10));
+
+        assert_eq!(clock.get_active_time(at(12), None), Duration::from_secs(3));
+        assert_eq!(
+            clock.get_active_time(at(12), Some(Duration::from_secs(15))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            clock.get_active_time(at(30), Some(Duration::from_secs(15))),
+            Duration::from_secs(13)
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! Module with available test modes.
+//!
+//! Test mode decides when the test is finished:
+//! * `time` - test ends after `duration` seconds specified in config have passed,
+//! * `words` - test ends once the user has typed the amount of words specified in config,
+//! * `quote` - test ends once the user has typed the whole quote,
+//! * `text` - test ends once the user has typed the whole custom text or `duration` has passed,
+//!   whichever comes first,
+//! * `code` - test ends once the user has typed the whole code snippet,
+//! * `zen` - there is no expected input and no time limit, test ends once the user presses
+//!   the `finish` key (`<Enter>` by default),
+//! * `lesson` - test ends once the user has typed the amount of words specified in config,
+//!   words are generated from letters unlocked in previous lessons.
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Enum used in config for defining the end condition of the test.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum TestMode {
+    #[default]
+    Time,
+    Words,
+    Quote,
+    Text,
+    Code,
+    Zen,
+    Lesson,
+}
@@ -5,26 +5,35 @@
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Datelike, Local, Timelike};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, Event, KeyEventKind};
 use ratatui::{
     prelude::{Backend, Constraint, Direction, Layout, Rect},
-    style::{Style, Stylize},
-    widgets::{Bar, BarGroup, Block},
-    widgets::{BarChart, Paragraph},
-    Terminal,
+    style::{Color, Style, Stylize},
+    symbols::Marker,
+    widgets::{Axis, Bar, BarGroup, Block, Chart, Dataset, GraphType},
+    widgets::{BarChart, Paragraph, Wrap},
+    Frame, Terminal,
 };
 use serde::{Deserialize, Serialize};
 
 use std::{
     fs::{create_dir_all, File},
-    path::PathBuf,
+    path::{Path, PathBuf},
     thread::sleep,
     time::Duration,
 };
 
 use crate::{
+    code_input::CodeLanguage,
     config::Config,
+    dictionary::Language,
+    key_stats::KeysStats,
+    keybindings::{Action, KeyContext, Keybindings},
+    keyboard::{render_keyboard_heatmap, HeatmapMetric, KeyboardLayout},
+    keystrokes::{get_wpm_timeline, save_events, KeystrokeEvent},
+    markov_input::Generator,
     runner::{FrameWrapper, FrameWrapperInterface},
+    test_mode::TestMode,
 };
 
 /// TestResults struct is combining test statistics with configuration of the test.
@@ -33,6 +42,12 @@ pub struct TestResults {
     pub local_datetime: DateTime<Local>,
 
     pub wpm: Option<f64>,
+    pub raw_wpm: Option<f64>,
+    pub cpm: Option<f64>,
+    // time from the first keystroke to the end of the test and total time of pauses, in seconds
+    pub active_time: Option<f64>,
+    pub paused_time: Option<f64>,
+    pub pauses_count: Option<u64>,
     pub raw_accuracy: Option<f64>,
     pub raw_valid_characters_count: Option<u64>,
     pub raw_mistakes_count: Option<u64>,
@@ -41,15 +56,53 @@ pub struct TestResults {
     pub valid_characters_count: Option<u64>,
     pub typed_characters_count: Option<u64>,
     pub mistakes_count: Option<u64>,
+    pub keystroke_interval: Option<f64>,
+    pub keystroke_interval_deviation: Option<f64>,
+    pub events_path: Option<PathBuf>,
+    #[serde(default, with = "crate::key_stats::json_column")]
+    pub keys_stats: KeysStats,
 
+    pub mode: Option<TestMode>,
     pub duration: Option<u64>,
+    pub words: Option<usize>,
+    pub quote_id: Option<usize>,
+    pub quote_source: Option<String>,
+    pub text_path: Option<String>,
+    pub text_hash: Option<String>,
+    pub code_language: Option<CodeLanguage>,
+    pub seed: Option<u64>,
+    pub lesson_letters: Option<String>,
+    pub daily: Option<bool>,
     pub numbers: Option<bool>,
     pub numbers_ratio: Option<f64>,
+    pub punctuation: Option<bool>,
     pub dictionary_path: Option<String>,
+    pub language: Option<Language>,
+    pub generator: Option<Generator>,
     pub uppercase: Option<bool>,
     pub uppercase_ratio: Option<f64>,
     pub results_path: Option<PathBuf>,
 
+    // stats of each line of the expected input, only displayed and not saved.
+    #[serde(skip)]
+    pub lines: Vec<LineStats>,
+
+    // unlocked letters and the next target in `lesson` mode, only displayed and not saved.
+    #[serde(skip)]
+    pub lesson_progress: Option<String>,
+
+    // today's best score and the daily streak after a daily challenge, only displayed and not saved.
+    #[serde(skip)]
+    pub daily_summary: Option<String>,
+
+    // layout of the keyboard used to display `keys_stats`, only displayed and not saved.
+    #[serde(skip)]
+    pub keyboard_layout: KeyboardLayout,
+
+    // keystroke event log of the test, saved to a separate file under `events_path`.
+    #[serde(skip)]
+    pub events: Vec<KeystrokeEvent>,
+
     // tells if test was successfully completed and results should be displayed and saved.
     #[serde(skip)]
     pub completed: bool,
@@ -57,10 +110,25 @@ pub struct TestResults {
     pub save: bool,
 }
 
+/// Action chosen by the user on the screen with results of the test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultsAction {
+    /// start the test again with the same text
+    Repeat,
+    /// start a test with a new text
+    Next,
+    Quit,
+}
+
 /// Struct holding numeric test results.
 #[derive(Debug)]
 pub struct Stats {
     pub wpm: f64,
+    pub raw_wpm: f64,
+    pub cpm: f64,
+    pub active_time: Duration,
+    pub paused_time: Duration,
+    pub pauses_count: u64,
     pub raw_accuracy: f64,
     pub raw_valid_characters_count: u64,
     pub raw_mistakes_count: u64,
@@ -69,12 +137,41 @@ pub struct Stats {
     pub valid_characters_count: u64,
     pub typed_characters_count: u64,
     pub mistakes_count: u64,
+    pub keystroke_interval: f64,
+    pub keystroke_interval_deviation: f64,
+    pub lines: Vec<LineStats>,
+    pub keys_stats: KeysStats,
+    pub events: Vec<KeystrokeEvent>,
+}
+
+/// Struct holding numeric results of a single line of the expected input.
+#[derive(Debug, Default, Clone)]
+pub struct LineStats {
+    pub valid_characters_count: u64,
+    pub mistakes_count: u64,
+}
+
+impl LineStats {
+    /// ratio of valid characters to typed characters in the line
+    pub fn get_accuracy(&self) -> f64 {
+        let typed_characters_count = self.valid_characters_count + self.mistakes_count;
+        if typed_characters_count == 0 {
+            return 0.0;
+        }
+
+        self.valid_characters_count as f64 / typed_characters_count as f64 * 100.0
+    }
 }
 
 impl Stats {
     pub fn default() -> Self {
         Stats {
             wpm: 0.0,
+            raw_wpm: 0.0,
+            cpm: 0.0,
+            active_time: Duration::from_secs(0),
+            paused_time: Duration::from_secs(0),
+            pauses_count: 0,
             raw_accuracy: 0.0,
             raw_valid_characters_count: 0,
             raw_mistakes_count: 0,
@@ -83,6 +180,11 @@ impl Stats {
             valid_characters_count: 0,
             mistakes_count: 0,
             typed_characters_count: 0,
+            keystroke_interval: 0.0,
+            keystroke_interval_deviation: 0.0,
+            lines: vec![],
+            keys_stats: KeysStats::default(),
+            events: vec![],
         }
     }
 }
@@ -90,50 +192,114 @@ impl Stats {
 impl TestResults {
     /// creates TestResults object from Stats and Config
     pub fn new(stats: Stats, config: Config, completed: bool) -> Self {
-        fn get_dictionary_path(dictionary_path: Option<PathBuf>) -> Option<String> {
-            if let Some(path) = dictionary_path {
-                if let Some(str) = path.to_str() {
-                    return Some(str.to_string());
-                }
-            }
-            Some("default_dictionary".to_string())
-        }
+        // there is no expected input in `zen` mode so accuracy can't be measured
+        let has_accuracy = config.mode != TestMode::Zen;
 
         TestResults {
             local_datetime: Local::now(),
             // stats
             wpm: Some(stats.wpm),
-            raw_accuracy: Some(stats.raw_accuracy),
-            raw_valid_characters_count: Some(stats.raw_valid_characters_count),
-            raw_mistakes_count: Some(stats.raw_mistakes_count),
+            raw_wpm: Some(stats.raw_wpm),
+            cpm: Some(stats.cpm),
+            active_time: Some(stats.active_time.as_secs_f64()),
+            paused_time: Some(stats.paused_time.as_secs_f64()),
+            pauses_count: Some(stats.pauses_count),
+            raw_accuracy: has_accuracy.then_some(stats.raw_accuracy),
+            raw_valid_characters_count: has_accuracy.then_some(stats.raw_valid_characters_count),
+            raw_mistakes_count: has_accuracy.then_some(stats.raw_mistakes_count),
             raw_typed_characters_count: Some(stats.raw_typed_characters_count),
-            accuracy: Some(stats.accuracy),
-            valid_characters_count: Some(stats.valid_characters_count),
+            accuracy: has_accuracy.then_some(stats.accuracy),
+            valid_characters_count: has_accuracy.then_some(stats.valid_characters_count),
             typed_characters_count: Some(stats.typed_characters_count),
-            mistakes_count: Some(stats.mistakes_count),
+            mistakes_count: has_accuracy.then_some(stats.mistakes_count),
+            keystroke_interval: Some(stats.keystroke_interval),
+            keystroke_interval_deviation: Some(stats.keystroke_interval_deviation),
+            lines: stats.lines,
+            keys_stats: stats.keys_stats,
+            events: stats.events,
+            // set when saving the results
+            events_path: None,
             // config
-            duration: Some(config.duration.as_secs()),
+            mode: Some(config.mode),
+            duration: match config.mode {
+                TestMode::Time | TestMode::Text => Some(config.duration.as_secs()),
+                TestMode::Words
+                | TestMode::Quote
+                | TestMode::Code
+                | TestMode::Zen
+                | TestMode::Lesson => None,
+            },
+            words: match config.mode {
+                TestMode::Words | TestMode::Lesson => Some(config.words),
+                TestMode::Time
+                | TestMode::Quote
+                | TestMode::Text
+                | TestMode::Code
+                | TestMode::Zen => None,
+            },
+            code_language: match config.mode {
+                TestMode::Code if config.code_path.is_none() => Some(config.code_language),
+                _ => None,
+            },
+            seed: config.seed,
+            // set by the caller, who knows which quote or text was used in the test
+            quote_id: None,
+            quote_source: None,
+            text_path: None,
+            text_hash: None,
+            lesson_letters: None,
+            lesson_progress: None,
+            daily: config.daily.then_some(true),
+            // set by the caller, who reads the previous results
+            daily_summary: None,
             numbers: Some(config.numbers),
             numbers_ratio: Some(config.numbers_ratio),
-            dictionary_path: get_dictionary_path(config.dictionary_path),
+            punctuation: Some(config.punctuation),
+            // builtin dictionary of the language is used only when there is no dictionary file
+            language: config.dictionary_path.is_none().then_some(config.language),
+            generator: match config.mode {
+                TestMode::Time | TestMode::Words => Some(config.generator),
+                _ => None,
+            },
+            dictionary_path: config
+                .dictionary_path
+                .and_then(|path| path.to_str().map(|str| str.to_string())),
             uppercase: Some(config.uppercase),
             uppercase_ratio: Some(config.uppercase_ratio),
 
             completed,
             save: config.save_results,
             results_path: config.results_path,
+            keyboard_layout: config.keyboard_layout,
         }
     }
 
     /// saves test statistics and configuration to a file in users home directory
-    pub fn save_to_file(&self) -> Result<(), anyhow::Error> {
+    ///
+    /// Keystroke event log is saved to a separate file in the `events` directory next to the
+    /// results file, and its path is saved together with the statistics.
+    pub fn save_to_file(&mut self) -> Result<(), anyhow::Error> {
         let default_results_path =
             get_results_file_path().context("Unable to ge results file path")?;
         let results_file_path = match &self.results_path {
-            Some(results_path) => results_path,
-            None => &default_results_path,
+            Some(results_path) => results_path.clone(),
+            None => default_results_path,
         };
 
+        if !self.events.is_empty() {
+            let events_dir_path = results_file_path
+                .parent()
+                .unwrap_or(Path::new(""))
+                .join("events");
+            let events_path = save_events(
+                &events_dir_path,
+                &self.local_datetime.format("%Y%m%d-%H%M%S%3f").to_string(),
+                &self.events,
+            )
+            .context("Unable to save keystroke events")?;
+            self.events_path = Some(events_path);
+        }
+
         let results = read_previous_results().context("Unable to read previous results")?;
 
         let mut writer =
@@ -158,9 +324,24 @@ impl TestResults {
 
     /// slightly modified version of `render_results` function
     /// uses different layout and renders current test stats in addition to previous results
-    pub fn render<B: Backend>(&self, terminal: &mut Terminal<B>) -> Result<()> {
+    ///
+    /// Returns the action the user chose to take after the test.
+    pub fn render<B: Backend>(
+        &self,
+        terminal: &mut Terminal<B>,
+        keybindings: &Keybindings,
+    ) -> Result<ResultsAction> {
         let mut results = read_previous_results().context("Unable to read previous results")?;
         results.push(self.clone());
+        let mut heatmap_metric = HeatmapMetric::Accuracy;
+        let help_message = format!(
+            "Press '{}' to toggle accuracy or time per key, \
+            '{}' to repeat the test, '{}' for a new test, '{}' to quit",
+            keybindings.describe(Action::ToggleHeatmap),
+            keybindings.describe(Action::Repeat),
+            keybindings.describe(Action::Next),
+            keybindings.describe(Action::Quit),
+        );
 
         loop {
             terminal.draw(|frame| {
@@ -177,7 +358,12 @@ impl TestResults {
                             Constraint::Length(1),
                             Constraint::Length(1),
                             Constraint::Length(1),
+                            Constraint::Length(1),
+                            Constraint::Length(1),
+                            Constraint::Length(2),
                             Constraint::Length(2),
+                            Constraint::Length(10),
+                            Constraint::Length(7),
                             Constraint::Length(12),
                             Constraint::Length(1),
                             Constraint::Length(1),
@@ -187,26 +373,48 @@ impl TestResults {
                     )
                     .split(frame.area());
 
-                frame.render_widget(Paragraph::new("Test completed"), areas[0]);
+                let title = match &self.quote_source {
+                    Some(quote_source) if !quote_source.is_empty() => {
+                        format!("Test completed - quote by {}", quote_source)
+                    }
+                    _ => "Test completed".to_string(),
+                };
+                let title = match &self.daily_summary {
+                    Some(daily_summary) => format!("{}\n{}", title, daily_summary),
+                    None => title,
+                };
+                frame.render_widget(Paragraph::new(title), areas[0]);
                 frame.render_widget(
-                    Paragraph::new("Press 'q' to quit")
+                    Paragraph::new(help_message.as_str())
                         .alignment(ratatui::prelude::Alignment::Right)
                         .green(),
                     areas[0],
                 );
 
+                self.render_wpm_chart(frame, areas[13]);
+
                 let mut frame_wrapper = FrameWrapper::new(frame);
-                self.render_stats(&mut frame_wrapper, &areas[1..10]);
-                render_chart(&mut frame_wrapper, &areas[10..14], &results);
+                self.render_stats(&mut frame_wrapper, &areas[1..12]);
+                self.render_lines_stats(&mut frame_wrapper, areas[12]);
+                self.render_lesson_progress(&mut frame_wrapper, areas[12]);
+                render_keyboard_heatmap(
+                    &mut frame_wrapper,
+                    areas[14],
+                    &self.keys_stats,
+                    self.keyboard_layout,
+                    heatmap_metric,
+                );
+                render_chart(&mut frame_wrapper, &areas[15..19], &results);
             })?;
 
             if event::poll(Duration::from_millis(100)).context("Unable to poll for event")? {
                 if let Event::Key(key) = event::read().context("Unable to read event")? {
                     if key.kind == KeyEventKind::Press {
-                        match key.code {
-                            KeyCode::Char('q') => {
-                                break;
-                            }
+                        match keybindings.get_action(&key, KeyContext::Results) {
+                            Some(Action::Repeat) => return Ok(ResultsAction::Repeat),
+                            Some(Action::Next) => return Ok(ResultsAction::Next),
+                            Some(Action::Quit) => return Ok(ResultsAction::Quit),
+                            Some(Action::ToggleHeatmap) => heatmap_metric = heatmap_metric.toggle(),
                             _ => {}
                         }
                     }
@@ -214,84 +422,210 @@ impl TestResults {
             }
             sleep(Duration::from_millis(100));
         }
-
-        Ok(())
     }
 
     /// renders numeric statistics of the current test
+    ///
+    /// Statistics that are not available for the test, e.g. accuracy in `zen` mode,
+    /// are skipped and following ones are moved up.
     fn render_stats(&self, frame: &mut impl FrameWrapperInterface, areas: &[Rect]) {
+        let mut lines: Vec<String> = vec![];
+
         if let Some(wpm) = self.wpm {
-            frame.render_widget(Paragraph::new(format!("WPM: {:.2}", wpm)), areas[0]);
+            let speed = match (self.raw_wpm, self.cpm) {
+                (Some(raw_wpm), Some(cpm)) => {
+                    format!("WPM: {:.2} (raw WPM: {:.2}, CPM: {:.0})", wpm, raw_wpm, cpm)
+                }
+                _ => format!("WPM: {:.2}", wpm),
+            };
+            lines.push(speed);
+        }
+        if let Some(active_time) = self.active_time {
+            let pauses = match (self.pauses_count, self.paused_time) {
+                (Some(pauses_count), Some(paused_time)) if pauses_count > 0 => {
+                    let label = match pauses_count {
+                        1 => "pause",
+                        _ => "pauses",
+                    };
+                    format!(", {} {label} ({:.1} s)", pauses_count, paused_time)
+                }
+                _ => String::new(),
+            };
+            lines.push(format!("Typing time: {:.1} s{}", active_time, pauses));
         }
         if let Some(raw_accuracy) = self.raw_accuracy {
-            frame.render_widget(
-                Paragraph::new(format!("Raw accuracy: {:.2}%", raw_accuracy)),
-                areas[1],
-            );
+            lines.push(format!("Raw accuracy: {:.2}%", raw_accuracy));
         }
         if let Some(raw_valid_characters_count) = self.raw_valid_characters_count {
-            frame.render_widget(
-                Paragraph::new(format!(
-                    "Raw valid characters: {}",
-                    raw_valid_characters_count
-                )),
-                areas[2],
-            );
+            lines.push(format!(
+                "Raw valid characters: {}",
+                raw_valid_characters_count
+            ));
         }
         if let Some(raw_mistakes_count) = self.raw_mistakes_count {
-            frame.render_widget(
-                Paragraph::new(format!("Raw mistakes: {}", raw_mistakes_count)),
-                areas[3],
-            );
+            lines.push(format!("Raw mistakes: {}", raw_mistakes_count));
         }
         if let Some(raw_typed_characters_count) = self.raw_typed_characters_count {
-            frame.render_widget(
-                Paragraph::new(format!(
-                    "Raw characters typed: {}",
-                    raw_typed_characters_count
-                )),
-                areas[4],
-            );
+            lines.push(format!(
+                "Raw characters typed: {}",
+                raw_typed_characters_count
+            ));
         }
         if let Some(accuracy) = self.accuracy {
-            frame.render_widget(
-                Paragraph::new(format!("Accuracy after corrections: {:.2}%", accuracy)),
-                areas[5],
-            );
+            lines.push(format!("Accuracy after corrections: {:.2}%", accuracy));
         }
         if let Some(valid_characters_count) = self.valid_characters_count {
-            frame.render_widget(
-                Paragraph::new(format!(
-                    "Valid characters after corrections: {}",
-                    valid_characters_count
-                )),
-                areas[6],
-            );
+            lines.push(format!(
+                "Valid characters after corrections: {}",
+                valid_characters_count
+            ));
         }
         if let Some(mistakes_count) = self.mistakes_count {
-            frame.render_widget(
-                Paragraph::new(format!("Mistakes after corrections: {}", mistakes_count)),
-                areas[7],
-            );
+            lines.push(format!("Mistakes after corrections: {}", mistakes_count));
         }
-
         if let Some(typed_characters_count) = self.typed_characters_count {
+            lines.push(format!(
+                "Characters typed after corrections: {}",
+                typed_characters_count,
+            ));
+        }
+        if let (Some(keystroke_interval), Some(keystroke_interval_deviation)) =
+            (self.keystroke_interval, self.keystroke_interval_deviation)
+        {
+            lines.push(format!(
+                "Keystroke rhythm: {:.0} ms between keystrokes (± {:.0} ms)",
+                keystroke_interval, keystroke_interval_deviation
+            ));
+        }
+
+        for (line, area) in lines.into_iter().zip(areas) {
+            frame.render_widget(Paragraph::new(line), *area);
+        }
+    }
+
+    /// renders Chart widget from ratatui crate
+    /// displaying WPM and raw WPM of each second of the test
+    /// and marking seconds in which mistakes were made.
+    ///
+    /// Datasets of the chart borrow the data, so it's rendered directly on the frame.
+    fn render_wpm_chart(&self, frame: &mut Frame, area: Rect) {
+        let timeline = get_wpm_timeline(&self.events);
+        if timeline.is_empty() {
+            return;
+        }
+
+        let wpm_data = timeline
+            .iter()
+            .map(|sample| (sample.second as f64, sample.wpm))
+            .collect::<Vec<_>>();
+        let raw_wpm_data = timeline
+            .iter()
+            .map(|sample| (sample.second as f64, sample.raw_wpm))
+            .collect::<Vec<_>>();
+        let mistakes_data = timeline
+            .iter()
+            .filter(|sample| sample.mistakes_count > 0)
+            .map(|sample| (sample.second as f64, sample.raw_wpm))
+            .collect::<Vec<_>>();
+
+        let seconds = timeline.len() as f64;
+        let max_wpm = timeline
+            .iter()
+            .map(|sample| sample.wpm.max(sample.raw_wpm))
+            .fold(0.0, f64::max);
+        // round the top of the chart up to tens, so that labels are readable
+        let max_wpm = ((max_wpm / 10.0).ceil() * 10.0).max(10.0);
+
+        let datasets = vec![
+            Dataset::default()
+                .name("raw")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::DarkGray))
+                .data(&raw_wpm_data),
+            Dataset::default()
+                .name("wpm")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Green))
+                .data(&wpm_data),
+            Dataset::default()
+                .name("mistakes")
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(Color::Red))
+                .data(&mistakes_data),
+        ];
+
+        frame.render_widget(
+            Chart::new(datasets)
+                .block(Block::default().title("WPM over time:"))
+                .x_axis(
+                    Axis::default()
+                        .bounds([1.0, seconds.max(2.0)])
+                        .labels(["1s".to_string(), format!("{}s", seconds)]),
+                )
+                .y_axis(Axis::default().bounds([0.0, max_wpm]).labels([
+                    "0".to_string(),
+                    format!("{:.0}", max_wpm / 2.0),
+                    format!("{:.0}", max_wpm),
+                ])),
+            area,
+        );
+    }
+
+    /// renders unlocked letters and the target needed to unlock the next one in `lesson` mode
+    fn render_lesson_progress(&self, frame: &mut impl FrameWrapperInterface, area: Rect) {
+        if let Some(lesson_progress) = &self.lesson_progress {
             frame.render_widget(
-                Paragraph::new(format!(
-                    "Characters typed after corrections: {}",
-                    typed_characters_count,
-                )),
-                areas[8],
+                Paragraph::new(lesson_progress.clone()).wrap(Wrap { trim: true }),
+                area,
             );
         }
     }
+
+    /// renders accuracy after corrections of each line of the expected input
+    fn render_lines_stats(&self, frame: &mut impl FrameWrapperInterface, area: Rect) {
+        if self.lines.is_empty() {
+            return;
+        }
+
+        let lines_accuracy = self
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| format!("{}: {:.0}%", index + 1, line.get_accuracy()))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        frame.render_widget(
+            Paragraph::new(format!("Accuracy per line: {}", lines_accuracy))
+                .wrap(Wrap { trim: true }),
+            area,
+        );
+    }
 }
 
 /// creates rendering loop and passes provided test results vector to render_chart function
+///
+/// Statistics of each key are summed up across all results and displayed on a keyboard diagram.
 pub fn render_results<B: Backend>(
     terminal: &mut Terminal<B>,
-    results: &Vec<TestResults>,
+    results: &[TestResults],
+    keyboard_layout: KeyboardLayout,
+    keybindings: &Keybindings,
 ) -> Result<()> {
+    let mut keys_stats = KeysStats::default();
+    for result in results {
+        keys_stats.merge(&result.keys_stats);
+    }
+    let mut heatmap_metric = HeatmapMetric::Accuracy;
+    let help_message = format!(
+        "Press '{}' to toggle accuracy or time per key, '{}' to quit",
+        keybindings.describe(Action::ToggleHeatmap),
+        keybindings.describe(Action::Quit),
+    );
+
     loop {
         terminal.draw(|frame| {
             let areas = Layout::default()
@@ -302,6 +636,8 @@ pub fn render_results<B: Backend>(
                         Constraint::Length(12),
                         Constraint::Length(1),
                         Constraint::Length(1),
+                        Constraint::Length(2),
+                        Constraint::Length(6),
                         Constraint::Min(1),
                     ]
                     .as_ref(),
@@ -309,23 +645,29 @@ pub fn render_results<B: Backend>(
                 .split(frame.area());
 
             frame.render_widget(
-                Paragraph::new("Press 'q' to quit")
+                Paragraph::new(help_message.as_str())
                     .alignment(ratatui::prelude::Alignment::Right)
                     .green(),
                 areas[0],
             );
 
             let mut frame_wrapper = FrameWrapper::new(frame);
-            render_chart(&mut frame_wrapper, &areas[1..5], &results);
+            render_chart(&mut frame_wrapper, &areas[1..5], results);
+            render_keyboard_heatmap(
+                &mut frame_wrapper,
+                areas[5],
+                &keys_stats,
+                keyboard_layout,
+                heatmap_metric,
+            );
         })?;
 
         if event::poll(Duration::from_millis(100)).context("Unable to poll for event")? {
             if let Event::Key(key) = event::read().context("Unable to read event")? {
                 if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') => {
-                            break;
-                        }
+                    match keybindings.get_action(&key, KeyContext::History) {
+                        Some(Action::Quit) => break,
+                        Some(Action::ToggleHeatmap) => heatmap_metric = heatmap_metric.toggle(),
                         _ => {}
                     }
                 }
@@ -340,12 +682,8 @@ pub fn render_results<B: Backend>(
 /// renders BarChart widget from ratatui crate
 /// displaying WPM values of provided TestResults
 /// and adding dates of the tests as their custom labels.
-fn render_chart(
-    frame: &mut impl FrameWrapperInterface,
-    areas: &[Rect],
-    results: &Vec<TestResults>,
-) {
-    let mut results_to_render = results.clone();
+fn render_chart(frame: &mut impl FrameWrapperInterface, areas: &[Rect], results: &[TestResults]) {
+    let mut results_to_render = results.to_vec();
     let bar_width = 5;
     let frame_width = frame.area().width;
     let bars_to_show = ((frame_width + 1) / (bar_width + 1)) as usize;
@@ -413,7 +751,7 @@ fn render_chart(
     );
 }
 
-fn get_results_dir_path() -> Result<PathBuf> {
+pub fn get_results_dir_path() -> Result<PathBuf> {
     let dir_path = if cfg!(target_os = "windows") {
         dirs::config_local_dir().context("Unable to get local config directory")?
     } else {
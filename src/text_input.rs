@@ -0,0 +1,175 @@
+//! Module creating the expected input for the test runner in `text` mode
+//!
+//! It reads the file provided as `text_path` in config, or standard input if the path is `-`,
+//! normalizes whitespace in it
+//! and returns the text in its original order, without shuffling or repeating it.
+//!
+//! If `keep_newlines` is set to true in config line breaks are kept in the text
+//! and have to be typed with `<Enter>`.
+//!
+//! Standard input can be read only once, so it's read at the start of the session
+//! and kept in config for every test of the session.
+
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::expected_input::ExpectedInputInterface;
+use crate::helpers::{get_chars_range, get_hash};
+
+/// Struct used by runner to hold the custom text used for validation and as a placeholder
+#[derive(Debug)]
+pub struct ExpectedText {
+    pub path: String,
+    pub hash: String,
+    text: Vec<char>,
+}
+
+impl ExpectedText {
+    /// Create new struct instance by reading the text file or standard input
+    ///
+    /// Hash is calculated from the original content of the file, before normalizing it.
+    pub fn new(config: &Config) -> Result<Self> {
+        let text_path = config
+            .text_path
+            .as_deref()
+            .context("Text path is required in text mode")?;
+
+        let mut content = String::new();
+        if text_path == Path::new("-") {
+            match &config.stdin_text {
+                Some(stdin_text) => content.clone_from(stdin_text),
+                None => {
+                    std::io::stdin()
+                        .read_to_string(&mut content)
+                        .context("Unable to read text from standard input")?;
+                }
+            }
+        } else {
+            let mut file = std::fs::File::open(text_path).context("Unable to open text file")?;
+            file.read_to_string(&mut content)
+                .context("Unable to read text file")?;
+        }
+
+        let text = normalize_whitespace(&content, config.keep_newlines);
+        if text.is_empty() {
+            anyhow::bail!("Text file is empty");
+        }
+
+        Ok(Self {
+            path: text_path.display().to_string(),
+            hash: get_hash(&content),
+            text: text.chars().collect(),
+        })
+    }
+}
+
+/// Reads the text from `reader` to config if `text_path` is `-` and it wasn't read yet
+pub fn read_stdin_text(config: &mut Config, mut reader: impl Read) -> Result<()> {
+    if config.text_path.as_deref() != Some(Path::new("-")) || config.stdin_text.is_some() {
+        return Ok(());
+    }
+
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .context("Unable to read text from standard input")?;
+    config.stdin_text = Some(content);
+
+    Ok(())
+}
+
+/// Collapses whitespace into single spaces and trims the text.
+///
+/// With `keep_newlines` each non-empty line is normalized separately and lines are joined with
+/// `\n`.
+fn normalize_whitespace(content: &str, keep_newlines: bool) -> String {
+    fn normalize_line(line: &str) -> String {
+        line.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    match keep_newlines {
+        true => content
+            .lines()
+            .map(normalize_line)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        false => normalize_line(content),
+    }
+}
+
+impl ExpectedInputInterface for ExpectedText {
+    /// Returns characters of the text from the given range
+    ///
+    /// If the text ends before the end of the range, the rest of the text is returned.
+    fn get_range(&mut self, start: usize, end: usize) -> String {
+        get_chars_range(&self.text, start, end)
+    }
+
+    fn get_len(&self) -> Option<usize> {
+        Some(self.text.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn get_config(content: &str, keep_newlines: bool) -> (Config, tempfile::NamedTempFile) {
+        let mut text_file = tempfile::NamedTempFile::new().expect("Unable to create temp file");
+        text_file
+            .write_all(content.as_bytes())
+            .expect("Unable to write to temp file");
+
+        (
+            Config {
+                text_path: Some(text_file.path().to_path_buf()),
+                keep_newlines,
+                ..Config::default()
+            },
+            text_file, // It keeps tmp file while test is running
+        )
+    }
+
+    #[test]
+    fn should_keep_text_order_and_normalize_whitespace() {
+        let (config, _text_file) = get_config("  foo\tbar\n\n baz  \n", false);
+        let mut expected_text = ExpectedText::new(&config).expect("unable to create expected text");
+
+        assert_eq!(expected_text.get_range(0, 100), "foo bar baz");
+        assert_eq!(expected_text.get_len(), Some(11));
+        assert_eq!(expected_text.hash, get_hash("  foo\tbar\n\n baz  \n"));
+    }
+
+    #[test]
+    fn should_keep_newlines() {
+        let (config, _text_file) = get_config("  foo\tbar\n\n baz  \n", true);
+        let mut expected_text = ExpectedText::new(&config).expect("unable to create expected text");
+
+        assert_eq!(expected_text.get_range(0, 100), "foo bar\nbaz");
+    }
+
+    #[test]
+    fn should_reuse_text_read_from_standard_input() {
+        let mut config = Config {
+            text_path: Some(PathBuf::from("-")),
+            ..Config::default()
+        };
+
+        read_stdin_text(&mut config, "foo  bar".as_bytes()).expect("unable to read text");
+        // standard input is already used up in the next tests of the session
+        read_stdin_text(&mut config, "".as_bytes()).expect("unable to read text");
+
+        for _ in 0..2 {
+            let mut expected_text =
+                ExpectedText::new(&config).expect("unable to create expected text");
+            assert_eq!(expected_text.get_range(0, 100), "foo bar");
+            assert_eq!(expected_text.path, "-");
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Module finding the keys and bigrams the user is weakest on.
+//!
+//! Statistics of each key and bigram are summed up across all previous tests
+//! and every letter and bigram gets a weakness score made of two parts:
+//! - error rate compared to the average error rate of all letters,
+//! - average time to press compared to the average time to press all letters.
+//!
+//! Letters and bigrams that were typed too few times are skipped, as their statistics are not
+//! reliable yet.
+//! Scores are used in `adaptive` mode to make words with weak letters and bigrams appear more
+//! often in the test.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::key_stats::{KeyStats, KeysStats};
+use crate::test_results::TestResults;
+
+/// Minimal amount of attempts of typing a letter or bigram to take it into consideration
+const MIN_ATTEMPTS_COUNT: u64 = 5;
+
+/// Amount of weakest letters and bigrams that are practised
+const WEAK_KEYS_COUNT: usize = 5;
+
+/// Weakest letters and bigrams with their weakness scores
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WeakKeys {
+    pub letters: HashMap<char, f64>,
+    pub bigrams: HashMap<String, f64>,
+}
+
+impl WeakKeys {
+    /// Finds weak letters and bigrams in statistics of previous tests
+    pub fn from_results(results: &[TestResults]) -> Self {
+        let mut keys_stats = KeysStats::default();
+        for result in results {
+            keys_stats.merge(&result.keys_stats);
+        }
+
+        Self::new(&keys_stats)
+    }
+
+    /// Finds weak letters and bigrams in given statistics
+    pub fn new(keys_stats: &KeysStats) -> Self {
+        let letters_stats = merge_lowercase(
+            keys_stats
+                .keys
+                .iter()
+                .filter(|(key, _)| key.is_alphabetic())
+                .map(|(key, key_stats)| (key.to_string(), key_stats)),
+        );
+        let bigrams_stats = merge_lowercase(
+            keys_stats
+                .bigrams
+                .iter()
+                .filter(|(bigram, _)| bigram.chars().all(char::is_alphabetic))
+                .map(|(bigram, bigram_stats)| (bigram.clone(), bigram_stats)),
+        );
+
+        // bigrams are compared against letters, as there is usually too little data for bigrams
+        let (average_error_rate, average_latency) = get_averages(&letters_stats);
+
+        let letters = get_weakest(&letters_stats, average_error_rate, average_latency)
+            .into_iter()
+            .filter_map(|(letter, score)| letter.chars().next().map(|letter| (letter, score)))
+            .collect();
+        let bigrams = get_weakest(&bigrams_stats, average_error_rate, average_latency)
+            .into_iter()
+            .collect();
+
+        Self { letters, bigrams }
+    }
+
+    /// Returns how much more often the word should appear in the test
+    ///
+    /// Word without any weak letters or bigrams has weight of `1.0`.
+    pub fn get_word_weight(&self, word: &str) -> f64 {
+        let word = word.to_lowercase();
+        let chars = word.chars().collect::<Vec<_>>();
+
+        let letters_score: f64 = self
+            .letters
+            .iter()
+            .filter(|(letter, _)| chars.contains(letter))
+            .map(|(_, score)| score)
+            .sum();
+        let bigrams_score: f64 = self
+            .bigrams
+            .iter()
+            .filter(|(bigram, _)| word.contains(bigram.as_str()))
+            .map(|(_, score)| score)
+            .sum();
+
+        1.0 + letters_score + bigrams_score
+    }
+}
+
+/// Sums up statistics of lowercase and uppercase versions of the same keys
+fn merge_lowercase<'a>(
+    keys_stats: impl Iterator<Item = (String, &'a KeyStats)>,
+) -> Vec<(String, KeyStats)> {
+    let mut merged_stats: BTreeMap<String, KeyStats> = BTreeMap::new();
+    for (key, key_stats) in keys_stats {
+        merged_stats
+            .entry(key.to_lowercase())
+            .or_default()
+            .merge(key_stats);
+    }
+
+    merged_stats.into_iter().collect()
+}
+
+fn get_error_rate(key_stats: &KeyStats) -> f64 {
+    1.0 - key_stats.get_accuracy() / 100.0
+}
+
+/// Returns average error rate and average time to press of given keys
+fn get_averages(keys_stats: &[(String, KeyStats)]) -> (f64, f64) {
+    let mut total = KeyStats::default();
+    for (_, key_stats) in keys_stats {
+        total.merge(key_stats);
+    }
+
+    (
+        get_error_rate(&total),
+        total.get_average_latency().unwrap_or(0.0),
+    )
+}
+
+/// Scores the keys and returns the weakest ones, which score above the average
+fn get_weakest(
+    keys_stats: &[(String, KeyStats)],
+    average_error_rate: f64,
+    average_latency: f64,
+) -> Vec<(String, f64)> {
+    let mut scores = keys_stats
+        .iter()
+        .filter(|(_, key_stats)| key_stats.hits + key_stats.misses >= MIN_ATTEMPTS_COUNT)
+        .map(|(key, key_stats)| {
+            let error_score = match average_error_rate > 0.0 {
+                true => get_error_rate(key_stats) / average_error_rate,
+                false => 0.0,
+            };
+            let latency_score = match key_stats.get_average_latency() {
+                Some(latency) if average_latency > 0.0 => latency / average_latency,
+                _ => 0.0,
+            };
+
+            // score of an average key is 2.0, only keys above it are weak
+            (key.clone(), error_score + latency_score - 2.0)
+        })
+        .filter(|(_, score)| *score > 0.0)
+        .collect::<Vec<_>>();
+
+    scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    scores.truncate(WEAK_KEYS_COUNT);
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn should_find_error_prone_and_slow_letters() {
+        let mut keys_stats = KeysStats::default();
+        for _ in 0..10 {
+            keys_stats.record('a', None, true, Some(Duration::from_millis(100)));
+            keys_stats.record('b', None, true, Some(Duration::from_millis(100)));
+            keys_stats.record('c', None, true, Some(Duration::from_millis(400)));
+            keys_stats.record('d', Some('c'), false, Some(Duration::from_millis(100)));
+        }
+        // not enough data
+        keys_stats.record('e', None, false, Some(Duration::from_millis(1000)));
+
+        let weak_keys = WeakKeys::new(&keys_stats);
+
+        assert_eq!(weak_keys.letters.len(), 2);
+        assert!(weak_keys.letters[&'d'] > weak_keys.letters[&'c']);
+        assert!(weak_keys.bigrams.contains_key("cd"));
+        assert_eq!(weak_keys.get_word_weight("ab"), 1.0);
+        assert!(weak_keys.get_word_weight("cd") > weak_keys.get_word_weight("da"));
+    }
+}
@@ -0,0 +1,21 @@
+//! Module with the expected input for the test runner in `zen` mode
+//!
+//! In `zen` mode user types freely, so there is no text to validate the input against
+//! and nothing is displayed as a placeholder.
+
+use crate::expected_input::ExpectedInputInterface;
+
+/// Struct used by runner in place of expected input in `zen` mode
+#[derive(Debug)]
+pub struct ExpectedNothing;
+
+impl ExpectedInputInterface for ExpectedNothing {
+    fn get_range(&mut self, _start: usize, _end: usize) -> String {
+        String::new()
+    }
+
+    /// Test in `zen` mode is finished by the user, not by reaching the end of expected input
+    fn get_len(&self) -> Option<usize> {
+        None
+    }
+}
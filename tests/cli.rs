@@ -1,5 +1,4 @@
 use assert_cmd::Command;
-use predicates;
 use std::io::Write;
 
 #[ignore = "broken on GitHub Actions"]